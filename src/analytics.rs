@@ -18,6 +18,18 @@ impl Analytics {
     }
 }
 
+impl Analytics {
+	/* Worst, average and best time, then worst space complexity */
+	pub fn summary(&self) -> [String; 4] {
+		[
+			self.worst.to_string(),
+			self.average.to_string(),
+			self.best.to_string(),
+			self.worst_space.to_string(),
+		]
+	}
+}
+
 impl Display for Analytics {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(f, "Worst time: {}\nAverage time: {}\nBest time: {}\nWorst space: {}\n", 
@@ -77,11 +89,14 @@ impl Display for Notation {
 }
 
 pub enum Rate {
-	#[allow(dead_code)] Constant,
+	Constant,
 	Linear,
 	Quadratic,
 	#[allow(dead_code)] LogN,
 	NLogN,
+	NLogSquaredN,
+	NPlusK,
+	NTimesK,
 	NNFact,
 	Infinite
 }
@@ -94,6 +109,9 @@ impl Display for Rate {
 			Rate::Quadratic => "n\u{00B2}",
 			Rate::LogN => "log n",
 			Rate::NLogN => "n log n",
+			Rate::NLogSquaredN => "n log\u{00B2} n",
+			Rate::NPlusK => "n + k",
+			Rate::NTimesK => "n \u{00D7} k",
 			Rate::NNFact => "n \u{00D7} n!",
 			Rate::Infinite => "\u{221E}",
 		})
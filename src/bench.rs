@@ -0,0 +1,139 @@
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{distribution::Distribution, event::SortEvent, export::Record, metrics::Metrics, sort::Sort, sort_type::SortType, BenchArgs, Control, Error, Renderer, SortOptions, BOGO_STEP_LIMIT};
+
+/* Renderer that discards all output, counting operations so a sort stopped early still has metrics */
+pub struct Headless {
+	metrics: Metrics,
+	step_limit: Option<usize>,
+}
+
+impl Headless {
+	/* Headless renderer for sort type, stopping bogosort at the step limit as it may never finish */
+	pub fn new(sort_type: SortType, options: &SortOptions) -> Headless {
+		Headless {
+			metrics: Metrics::new(sort_type, options),
+			step_limit: matches!(sort_type, SortType::Bogo).then_some(BOGO_STEP_LIMIT),
+		}
+	}
+}
+
+impl Renderer for Headless {
+	fn render(&mut self, event: &SortEvent) -> Result<(), Error> {
+		self.metrics.record(event);
+		Ok(())
+	}
+
+	fn sleep(&mut self, _duration: Duration) -> Result<Control, Error> {
+		if self.step_limit.is_some_and(|limit| self.metrics.steps() >= limit) {
+			Ok(Control::Quit)
+		} else {
+			Ok(Control::Continue)
+		}
+	}
+}
+
+
+/* Min, mean and max of a set of samples */
+struct Stats {
+	min: f64,
+	mean: f64,
+	max: f64,
+}
+
+impl Stats {
+	fn new(samples: impl Iterator<Item = f64>) -> Stats {
+		let (count, sum, min, max) = samples.fold(
+			(0, 0.0, f64::INFINITY, f64::NEG_INFINITY),
+			|(count, sum, min, max), x| (count + 1, sum + x, min.min(x), max.max(x))
+		);
+
+		Stats {
+			min,
+			mean: sum / count as f64,
+			max,
+		}
+	}
+}
+
+
+/* Run each sort type over random inputs of each quantity, printing a table of results to stderr if stdout is taken */
+pub fn run(args: BenchArgs, distribution: Distribution, seed: u64, options: SortOptions, stdout_taken: bool) -> Vec<Record> {
+	let mut records = Vec::new();
+	let mut rng = StdRng::seed_from_u64(seed);
+
+	let print_line = |line: String| if stdout_taken { eprintln!("{}", line) } else { println!("{}", line) };
+
+	print_line(format!("{:<16}{:>8}  {:<14}{:>12}{:>12}{:>12}", "Algorithm", "Items", "Counter", "Min", "Mean", "Max"));
+
+	for sort_type in &args.sort_types {
+		for quantity in &args.quantities {
+			let seeds: Vec<u64> = (0..args.runs).map(|_| rng.gen()).collect();
+
+			let results: Vec<(Metrics, Duration, bool)> = seeds.iter()
+				.map(|seed| measure(*sort_type, *quantity, distribution, *seed, options))
+				.collect();
+
+			print_results(*sort_type, *quantity, &results, &print_line);
+
+			records.extend(seeds.into_iter().zip(results)
+				.map(|(seed, (metrics, elapsed, finished))| Record::new(*sort_type, options.variant(*sort_type), *quantity, Some(distribution), seed, metrics, elapsed).finished(finished)));
+		}
+	}
+
+	records
+}
+
+/* Sort a single input generated from seed, timing the run and whether it finished before the step limit */
+fn measure(sort_type: SortType, quantity: usize, distribution: Distribution, seed: u64, options: SortOptions) -> (Metrics, Duration, bool) {
+	let data = distribution.generate(quantity, &mut StdRng::seed_from_u64(seed));
+	let mut headless = Headless::new(sort_type, &options);
+	let start = Instant::now();
+
+	let result = Sort::new(&mut headless, sort_type, data, 0, seed, options).run();
+	let elapsed = start.elapsed();
+
+	match result {
+		Ok(metrics) => (metrics, elapsed, true),
+		Err(Error::Interrupted) => (headless.metrics, elapsed, false),
+		Err(error) => panic!("headless sort failed: {:?}", error),
+	}
+}
+
+/* Print counter and timing rows for one algorithm and quantity over the runs that finished, then how many were stopped */
+fn print_results(sort_type: SortType, quantity: usize, results: &[(Metrics, Duration, bool)], print_line: &dyn Fn(String)) {
+	let finished: Vec<&(Metrics, Duration, bool)> = results.iter().filter(|(_, _, finished)| *finished).collect();
+	let mut lines: Vec<String> = Vec::new();
+
+	if let Some((first, _, _)) = finished.first() {
+		let mut rows: Vec<(&str, Stats)> = first.counters().iter()
+			.map(|(counter, _)| (counter.name(), Stats::new(
+				finished.iter().map(|(metrics, _, _)| metrics.get(*counter) as f64)
+			)))
+			.collect();
+
+		rows.push(("Time (\u{00B5}s)", Stats::new(
+			finished.iter().map(|(_, elapsed, _)| elapsed.as_secs_f64() * 1_000_000.0)
+		)));
+
+		lines.extend(rows.iter().map(|(name, stats)| format!("{:<14}{:>12.1}{:>12.1}{:>12.1}", name, stats.min, stats.mean, stats.max)));
+	}
+
+	/* Partial counts of stopped runs would skew the statistics */
+	let stopped = results.len() - finished.len();
+	if stopped > 0 {
+		lines.push(format!("{} of {} runs stopped after {} steps", stopped, results.len(), BOGO_STEP_LIMIT));
+	}
+
+	for (index, line) in lines.iter().enumerate() {
+		let (algorithm, items) = if index == 0 {
+			(sort_type.to_string(), quantity.to_string())
+		} else {
+			(String::new(), String::new())
+		};
+
+		print_line(format!("{:<16}{:>8}  {}", algorithm, items, line));
+	}
+}
@@ -0,0 +1,122 @@
+use clap::ValueEnum;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+/* Number of distinct values in few unique data */
+const FEW_UNIQUE_VALUES: u64 = 4;
+/* Number of ascending runs in sawtooth data */
+const SAWTOOTH_TEETH: u64 = 4;
+/* Nearly sorted data has one random swap per this many items */
+const NEARLY_SORTED_SPACING: usize = 10;
+
+/* Shape of generated data */
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+pub enum Distribution {
+	#[default]
+	Random,
+	Sorted,
+	Reversed,
+	NearlySorted,
+	FewUnique,
+	Sawtooth,
+	OrganPipe,
+	AllEqual,
+}
+
+impl Distribution {
+	/* Generate quantity values in range [1 - quantity] */
+	pub fn generate(&self, quantity: usize, rng: &mut StdRng) -> Vec<u64> {
+		let n = quantity as u64;
+
+		let mut data: Vec<u64> = match self {
+			Distribution::Random | Distribution::Sorted | Distribution::NearlySorted => (1..=n).collect(),
+			Distribution::Reversed => (1..=n).rev().collect(),
+			Distribution::FewUnique => (0..n)
+				.map(|i| ((i * FEW_UNIQUE_VALUES / n + 1) * n / FEW_UNIQUE_VALUES).max(1))
+				.collect(),
+			Distribution::Sawtooth => {
+				let tooth = n.div_ceil(SAWTOOTH_TEETH);
+				(0..n).map(|i| (i % tooth + 1) * n / tooth).collect()
+			},
+			Distribution::OrganPipe => (0..n).map(|i| 2 * i.min(n - 1 - i) + 1).collect(),
+			Distribution::AllEqual => vec![n.div_ceil(2); quantity],
+		};
+
+		match self {
+			Distribution::Random | Distribution::FewUnique => data.shuffle(rng),
+			Distribution::NearlySorted => {
+				for _ in 0 .. (quantity / NEARLY_SORTED_SPACING).max(1) {
+					data.swap(rng.gen_range(0..quantity), rng.gen_range(0..quantity));
+				}
+			},
+			_ => (),
+		}
+
+		data
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use rand::SeedableRng;
+
+	use super::*;
+
+	fn generate(distribution: Distribution, quantity: usize) -> Vec<u64> {
+		distribution.generate(quantity, &mut StdRng::seed_from_u64(0))
+	}
+
+	#[test]
+	fn generates_quantity_values_in_range() {
+		for distribution in Distribution::value_variants() {
+			for quantity in [2, 3, 7, 50, 1000] {
+				let data = generate(*distribution, quantity);
+
+				assert_eq!(data.len(), quantity, "{:?}", distribution);
+				assert!(data.iter().all(|value| (1..=quantity as u64).contains(value)), "{:?} gave {:?}", distribution, data);
+			}
+		}
+	}
+
+	#[test]
+	fn same_seed_generates_same_data() {
+		for distribution in Distribution::value_variants() {
+			assert_eq!(generate(*distribution, 100), generate(*distribution, 100));
+		}
+	}
+
+	#[test]
+	fn random_is_permutation() {
+		let mut data = generate(Distribution::Random, 100);
+		data.sort_unstable();
+		assert_eq!(data, (1..=100).collect::<Vec<u64>>());
+	}
+
+	#[test]
+	fn ordered_shapes() {
+		assert_eq!(generate(Distribution::Sorted, 5), vec![1, 2, 3, 4, 5]);
+		assert_eq!(generate(Distribution::Reversed, 5), vec![5, 4, 3, 2, 1]);
+		assert_eq!(generate(Distribution::OrganPipe, 6), vec![1, 3, 5, 5, 3, 1]);
+		assert_eq!(generate(Distribution::Sawtooth, 8), vec![4, 8, 4, 8, 4, 8, 4, 8]);
+		assert_eq!(generate(Distribution::AllEqual, 5), vec![3; 5]);
+	}
+
+	#[test]
+	fn few_unique_has_few_values() {
+		let mut data = generate(Distribution::FewUnique, 100);
+		data.sort_unstable();
+		data.dedup();
+		assert_eq!(data, vec![25, 50, 75, 100]);
+	}
+
+	#[test]
+	fn nearly_sorted_is_sorted_permutation_with_few_swaps() {
+		let data = generate(Distribution::NearlySorted, 100);
+		let misplaced = data.iter().zip(1..).filter(|(value, index)| **value != *index).count();
+		assert!(misplaced <= 2 * 100 / NEARLY_SORTED_SPACING);
+
+		let mut sorted = data.clone();
+		sorted.sort_unstable();
+		assert_eq!(sorted, (1..=100).collect::<Vec<u64>>());
+	}
+}
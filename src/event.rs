@@ -0,0 +1,48 @@
+use crate::{sort_type::SortType, SortOptions};
+
+/* Single operation performed by a sorting algorithm */
+#[derive(Debug, Clone)]
+pub enum SortEvent {
+	/* Sort is starting with the data generated from seed */
+	Start { sort_type: SortType, options: SortOptions, seed: u64, data: Vec<u64> },
+	/* Values at indices i and j were compared, read from the buffer copied from that index if there is one */
+	Compare(usize, usize),
+	/* Value at index was read on its own, without being compared */
+	Read(usize),
+	/* Values at indices i and j were swapped */
+	Swap(usize, usize),
+	/* Value was written to index */
+	Write { index: usize, value: u64 },
+	/* Value at index was chosen as the pivot */
+	Pivot(usize),
+	/* Insertion sort over every gap-th element started */
+	Gap(usize),
+	/* data[start..end) is a sorted run, replacing any runs within it */
+	Run(usize, usize),
+	/* data[0..end) now forms a max heap */
+	Heap(usize),
+	/* Empty buckets were allocated, with capacity for their contents */
+	Buckets { buckets: usize, capacity: usize },
+	/* New pass over the digits of the data started */
+	Pass(usize),
+	/* Value at index was appended to bucket */
+	Bucket { index: usize, bucket: usize },
+	/* Front value of bucket was removed and written to index */
+	Drain { bucket: usize, index: usize },
+	/* Algorithm started working on data[start..end) */
+	EnterRange(usize, usize),
+	/* Algorithm finished working on data[start..end) */
+	LeaveRange(usize, usize),
+	/* data[start..end) was copied into an auxiliary buffer */
+	Allocate(usize, usize),
+	/* Auxiliary buffer copied from data[start..end) was released */
+	Free(usize, usize),
+	/* Buffered copy of data[from] was written back to data[index] */
+	Restore { from: usize, index: usize },
+	/* Data was replaced with a shuffled permutation */
+	Shuffle(Vec<u64>),
+	/* Algorithm moved to line of its pseudocode, with the values of its variables */
+	Trace { line: usize, vars: Vec<(&'static str, usize)> },
+	/* Sort has completed */
+	Finish,
+}
@@ -0,0 +1,212 @@
+use std::{fs, io::{self, Write}, path::PathBuf, time::Duration};
+
+use clap::ValueEnum;
+
+use crate::{cli_name, distribution::Distribution, metrics::Metrics, sort_type::SortType, Args, Error};
+
+/* File format of exported results */
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum Format {
+	Json,
+	Csv,
+}
+
+/* Value of an exported field, quoted and escaped by each format */
+enum Value {
+	Str(String),
+	Int(u64),
+	Bool(bool),
+	/* Written to three decimal places */
+	Float(f64),
+}
+
+
+/* Results of a single sort run */
+pub struct Record {
+	sort_type: SortType,
+	variant: Option<String>,
+	quantity: usize,
+	distribution: Option<Distribution>,
+	seed: u64,
+	finished: bool,
+	metrics: Metrics,
+	elapsed: Duration,
+}
+
+impl Record {
+	pub fn new(sort_type: SortType, variant: Option<String>, quantity: usize, distribution: Option<Distribution>, seed: u64, metrics: Metrics, elapsed: Duration) -> Record {
+		Record {
+			sort_type,
+			variant,
+			quantity,
+			distribution,
+			seed,
+			finished: true,
+			metrics,
+			elapsed,
+		}
+	}
+
+	/* Mark whether the sort finished, or was stopped with its counts so far */
+	pub fn finished(mut self, finished: bool) -> Record {
+		self.finished = finished;
+		self
+	}
+
+	/* Field names and values, in output order */
+	fn fields(&self) -> Vec<(String, Value)> {
+		let mut fields = vec![
+			(String::from("algorithm"), Value::Str(cli_name(&self.sort_type))),
+			(String::from("variant"), Value::Str(self.variant.clone().unwrap_or_default())),
+			(String::from("quantity"), Value::Int(self.quantity as u64)),
+			(String::from("distribution"), Value::Str(self.distribution.as_ref().map_or(String::from("input"), cli_name))),
+			(String::from("seed"), Value::Int(self.seed)),
+			(String::from("finished"), Value::Bool(self.finished)),
+		];
+
+		fields.extend(self.metrics.all_counters().iter()
+			.map(|(counter, value)| (counter.name().to_lowercase().replace(' ', "_"), Value::Int(*value as u64))));
+
+		fields.push((String::from("elapsed_ms"), Value::Float(self.elapsed.as_secs_f64() * 1000.0)));
+		fields
+	}
+}
+
+
+/* Where and how to write results */
+pub struct Export {
+	format: Format,
+	output: Option<PathBuf>,
+}
+
+impl Export {
+	/* Export requested by arguments, with format inferred from output extension if not given */
+	pub fn from_args(args: &Args) -> Option<Export> {
+		let inferred = args.output.as_ref().map(|output| {
+			match output.extension() {
+				Some(extension) if extension.eq_ignore_ascii_case("csv") => Format::Csv,
+				_ => Format::Json,
+			}
+		});
+
+		Some(Export {
+			format: args.format.or(inferred)?,
+			output: args.output.clone(),
+		})
+	}
+
+	/* Whether records are written to stdout */
+	pub fn to_stdout(&self) -> bool {
+		self.output.is_none()
+	}
+
+	/* Write records to output file, or stdout if none given */
+	pub fn write(&self, records: &[Record]) -> Result<(), Error> {
+		let contents = match self.format {
+			Format::Json => json(records),
+			Format::Csv => csv(records),
+		};
+
+		match &self.output {
+			Some(path) => fs::write(path, contents)?,
+			None => io::stdout().write_all(contents.as_bytes())?,
+		}
+
+		Ok(())
+	}
+}
+
+
+/* Format records as a JSON array of objects */
+fn json(records: &[Record]) -> String {
+	let objects: Vec<String> = records.iter()
+		.map(|record| {
+			let fields: Vec<String> = record.fields().iter()
+				.map(|(name, value)| format!("{}: {}", json_string(name), json_value(value)))
+				.collect();
+
+			format!("  {{ {} }}", fields.join(", "))
+		})
+		.collect();
+
+	format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+/* Format records as CSV with a header row */
+fn csv(records: &[Record]) -> String {
+	let mut lines: Vec<String> = Vec::new();
+
+	if let Some(record) = records.first() {
+		lines.push(record.fields().iter()
+			.map(|(name, _)| name.as_str())
+			.collect::<Vec<&str>>()
+			.join(","));
+	}
+
+	lines.extend(records.iter().map(|record| record.fields().iter()
+		.map(|(_, value)| csv_value(value))
+		.collect::<Vec<String>>()
+		.join(",")));
+
+	lines.join("\n") + "\n"
+}
+
+/* Write value as JSON */
+fn json_value(value: &Value) -> String {
+	match value {
+		Value::Str(text) => json_string(text),
+		Value::Int(number) => number.to_string(),
+		Value::Bool(flag) => flag.to_string(),
+		Value::Float(number) => format!("{:.3}", number),
+	}
+}
+
+/* Quote text as a JSON string, escaping quotes, backslashes and control characters */
+fn json_string(text: &str) -> String {
+	let mut quoted = String::from("\"");
+
+	for c in text.chars() {
+		match c {
+			'"' => quoted.push_str("\\\""),
+			'\\' => quoted.push_str("\\\\"),
+			'\n' => quoted.push_str("\\n"),
+			'\r' => quoted.push_str("\\r"),
+			'\t' => quoted.push_str("\\t"),
+			c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+			c => quoted.push(c),
+		}
+	}
+
+	quoted.push('"');
+	quoted
+}
+
+/* Write value as a CSV field, quoting text and doubling any quotes inside it, so commas in variants don't split it */
+fn csv_value(value: &Value) -> String {
+	match value {
+		Value::Str(text) => format!("\"{}\"", text.replace('"', "\"\"")),
+		Value::Int(number) => number.to_string(),
+		Value::Bool(flag) => flag.to_string(),
+		Value::Float(number) => format!("{:.3}", number),
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escapes_json_strings() {
+		assert_eq!(json_string("last pivot, lomuto"), "\"last pivot, lomuto\"");
+		assert_eq!(json_string("a \"b\" \\ c\n\u{1}"), "\"a \\\"b\\\" \\\\ c\\n\\u0001\"");
+	}
+
+	#[test]
+	fn quotes_csv_text_only() {
+		assert_eq!(csv_value(&Value::Str(String::from("last pivot, lomuto"))), "\"last pivot, lomuto\"");
+		assert_eq!(csv_value(&Value::Str(String::from("say \"hi\""))), "\"say \"\"hi\"\"\"");
+		assert_eq!(csv_value(&Value::Int(42)), "42");
+		assert_eq!(csv_value(&Value::Float(1.23456)), "1.235");
+	}
+}
@@ -0,0 +1,92 @@
+use clap::ValueEnum;
+
+/* Empirically derived gaps extended by Ciura's sequence */
+const CIURA_GAPS: [usize; 8] = [1, 4, 10, 23, 57, 132, 301, 701];
+/* Growth factor used to extend Ciura's gaps */
+const CIURA_RATIO: f64 = 2.25;
+/* Growth factor of Tokuda's gaps */
+const TOKUDA_RATIO: f64 = 2.25;
+
+/* Gap sequence used by shell sort */
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum Gaps {
+	/* n/2, n/4, ..., 1 */
+	Shell,
+	/* 1, 4, 13, 40, ... (3k + 1) */
+	Knuth,
+	/* 1, 4, 10, 23, 57, ... */
+	#[default]
+	Ciura,
+	/* 1, 8, 23, 77, 281, ... (4^k + 3 * 2^(k-1) + 1) */
+	Sedgewick,
+	/* 1, 4, 9, 20, 46, ... (h = 2.25h + 1, rounded up) */
+	Tokuda,
+}
+
+impl Gaps {
+	/* Gaps smaller than len, largest first and ending with 1 */
+	pub fn sequence(&self, len: usize) -> Vec<usize> {
+		let mut gaps: Vec<usize> = match self {
+			Gaps::Shell => std::iter::successors(Some(len / 2), |gap| Some(gap / 2))
+				.take_while(|gap| *gap > 0)
+				.collect(),
+			Gaps::Knuth => std::iter::successors(Some(1), |gap| Some(3 * gap + 1))
+				.take_while(|gap| *gap < len)
+				.collect(),
+			Gaps::Ciura => {
+				let mut gaps = CIURA_GAPS.to_vec();
+				while let Some(&last) = gaps.last().filter(|last| **last < len) {
+					gaps.push((last as f64 * CIURA_RATIO) as usize);
+				}
+				gaps
+			},
+			Gaps::Sedgewick => std::iter::once(1)
+				.chain((1..).map(|k: u32| 4usize.pow(k) + 3 * 2usize.pow(k - 1) + 1))
+				.take_while(|gap| *gap < len)
+				.collect(),
+			Gaps::Tokuda => std::iter::successors(Some(1.0), |gap: &f64| Some(TOKUDA_RATIO * gap + 1.0))
+				.map(|gap| gap.ceil() as usize)
+				.take_while(|gap| *gap < len)
+				.collect(),
+		};
+
+		gaps.retain(|gap| *gap < len.max(2));
+		gaps.sort_unstable_by(|a, b| b.cmp(a));
+		gaps.dedup();
+
+		gaps
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sequences_for_hundred_items() {
+		assert_eq!(Gaps::Shell.sequence(100), vec![50, 25, 12, 6, 3, 1]);
+		assert_eq!(Gaps::Knuth.sequence(100), vec![40, 13, 4, 1]);
+		assert_eq!(Gaps::Ciura.sequence(100), vec![57, 23, 10, 4, 1]);
+		assert_eq!(Gaps::Sedgewick.sequence(100), vec![77, 23, 8, 1]);
+		assert_eq!(Gaps::Tokuda.sequence(100), vec![46, 20, 9, 4, 1]);
+	}
+
+	#[test]
+	fn ciura_extends_past_known_gaps() {
+		assert_eq!(Gaps::Ciura.sequence(2000), vec![1577, 701, 301, 132, 57, 23, 10, 4, 1]);
+	}
+
+	#[test]
+	fn sequences_descend_below_len_to_one() {
+		for gaps in Gaps::value_variants() {
+			for len in 2 ..= 5000 {
+				let sequence = gaps.sequence(len);
+
+				assert_eq!(sequence.last(), Some(&1), "{:?} for {}", gaps, len);
+				assert!(sequence.iter().all(|gap| *gap < len), "{:?} for {}", gaps, len);
+				assert!(sequence.windows(2).all(|pair| pair[0] > pair[1]), "{:?} for {}", gaps, len);
+			}
+		}
+	}
+}
@@ -0,0 +1,162 @@
+use crate::{event::SortEvent, sort::SortSnapshot};
+
+/* Least number of steps between stored snapshots, spaced further apart for larger data so keyframes don't outgrow the events */
+const KEYFRAME_INTERVAL: usize = 64;
+
+/* Recorded events of a sort, able to reconstruct any step */
+pub struct History {
+	events: Vec<SortEvent>,
+	keyframes: Vec<SortSnapshot>,
+	interval: usize,
+}
+
+impl History {
+	pub fn new() -> History {
+		History {
+			events: Vec::new(),
+			keyframes: Vec::new(),
+			interval: KEYFRAME_INTERVAL,
+		}
+	}
+
+	/* Record event along with the snapshot after applying it */
+	pub fn record(&mut self, event: &SortEvent, snapshot: &SortSnapshot) {
+		if let SortEvent::Start { data, .. } = event {
+			self.events.clear();
+			self.keyframes.clear();
+			self.interval = KEYFRAME_INTERVAL.max(data.len());
+		}
+
+		if self.events.len().is_multiple_of(self.interval) {
+			self.keyframes.push(snapshot.clone());
+		}
+
+		self.events.push(event.clone());
+	}
+
+	/* Number of recorded steps */
+	pub fn len(&self) -> usize {
+		self.events.len()
+	}
+
+	/* Steps that change the sort, leaving out traces, to move between on the timeline */
+	pub fn operations(&self) -> Vec<usize> {
+		self.events.iter()
+			.enumerate()
+			.filter(|(_, event)| !matches!(event, SortEvent::Trace { .. }))
+			.map(|(step, _)| step)
+			.collect()
+	}
+
+	/* Reconstruct snapshot after the given step */
+	pub fn snapshot_at(&self, step: usize) -> Option<SortSnapshot> {
+		let step = step.min(self.len().checked_sub(1)?);
+		let keyframe = step / self.interval;
+
+		let mut snapshot = self.keyframes[keyframe].clone();
+		for event in &self.events[keyframe * self.interval + 1 ..= step] {
+			snapshot.apply(event);
+		}
+
+		Some(snapshot)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use rand::{rngs::StdRng, SeedableRng};
+	use strum::IntoEnumIterator;
+
+	use super::*;
+	use crate::{distribution::Distribution, gaps::Gaps, merge::Merge, partition::{Partition, Pivot}, sort::Sort, sort_type::SortType, Control, Error, Renderer, SortOptions};
+
+	/* Records history of a sort alongside a copy of the live snapshot after every step */
+	struct Recorder {
+		snapshot: Option<SortSnapshot>,
+		history: History,
+		live: Vec<SortSnapshot>,
+	}
+
+	impl Renderer for Recorder {
+		fn render(&mut self, event: &SortEvent) -> Result<(), Error> {
+			if let SortEvent::Start { .. } = event {
+				self.live.clear();
+			}
+
+			match &mut self.snapshot {
+				Some(snapshot) => snapshot.apply(event),
+				None => self.snapshot = SortSnapshot::from_event(event),
+			}
+
+			let snapshot = self.snapshot.as_ref().unwrap();
+			self.history.record(event, snapshot);
+			self.live.push(snapshot.clone());
+
+			Ok(())
+		}
+
+		fn sleep(&mut self, _duration: Duration) -> Result<Control, Error> {
+			Ok(Control::Continue)
+		}
+	}
+
+	const OPTIONS: SortOptions = SortOptions {
+		radix_base: 10,
+		gaps: Gaps::Ciura,
+		merge: Merge::TopDown,
+		pivot: Pivot::Last,
+		partition: Partition::Lomuto,
+	};
+
+	fn sort(recorder: &mut Recorder, sort_type: SortType, quantity: usize) {
+		let data = Distribution::Random.generate(quantity, &mut StdRng::seed_from_u64(0));
+		Sort::new(recorder, sort_type, data, 1, 0, OPTIONS).run().unwrap();
+	}
+
+	fn assert_reconstructs(recorder: &Recorder, sort_type: SortType, quantity: usize) {
+		assert_eq!(recorder.history.len(), recorder.live.len());
+
+		for (step, live) in recorder.live.iter().enumerate() {
+			let snapshot = recorder.history.snapshot_at(step).unwrap();
+			let context = format!("{:?} of {} at step {}", sort_type, quantity, step);
+			let buffers = |snapshot: &SortSnapshot| snapshot.get_buffers().iter()
+				.map(|buffer| (buffer.get_start(), buffer.get_values().clone(), buffer.get_cursor()))
+				.collect::<Vec<_>>();
+
+			assert_eq!(snapshot.get_data(), live.get_data(), "{}", context);
+			assert_eq!(buffers(&snapshot), buffers(live), "{}", context);
+			assert_eq!(snapshot.get_stack(), live.get_stack(), "{}", context);
+			assert_eq!(snapshot.get_line(), live.get_line(), "{}", context);
+			assert_eq!(snapshot.get_vars(), live.get_vars(), "{}", context);
+			assert_eq!(snapshot.get_gap(), live.get_gap(), "{}", context);
+			assert_eq!(snapshot.get_runs(), live.get_runs(), "{}", context);
+			assert_eq!(snapshot.get_heap(), live.get_heap(), "{}", context);
+			assert_eq!(snapshot.get_buckets(), live.get_buckets(), "{}", context);
+			assert_eq!(snapshot.get_pass(), live.get_pass(), "{}", context);
+			assert_eq!(snapshot.is_finished(), live.is_finished(), "{}", context);
+			assert_eq!(snapshot.get_metrics().all_counters(), live.get_metrics().all_counters(), "{}", context);
+
+			for index in 0 .. quantity {
+				assert_eq!(snapshot.highlight(index), live.highlight(index), "{} index {}", context, index);
+				assert_eq!(snapshot.buffer_highlight(index), live.buffer_highlight(index), "{} index {}", context, index);
+			}
+		}
+	}
+
+	#[test]
+	fn reconstructs_every_step() {
+		let mut recorder = Recorder { snapshot: None, history: History::new(), live: Vec::new() };
+
+		/* Larger data spaces keyframes further apart, and the next sort must restore the usual interval */
+		for sort_type in SortType::iter().filter(|sort_type| !matches!(sort_type, SortType::Bogo)) {
+			for quantity in [100, KEYFRAME_INTERVAL, 20] {
+				sort(&mut recorder, sort_type, quantity);
+				assert_eq!(recorder.history.interval, KEYFRAME_INTERVAL.max(quantity));
+				assert_reconstructs(&recorder, sort_type, quantity);
+			}
+		}
+	}
+}
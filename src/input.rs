@@ -0,0 +1,100 @@
+use std::{fs, io, path::Path};
+
+use crate::{Error, MAX_QUANTITY, MAX_VALUE, MIN_QUANTITY};
+
+/* Read data from file, or stdin if path is "-" */
+pub fn read(path: &Path) -> Result<Vec<u64>, Error> {
+	let contents = if path == Path::new("-") {
+		io::read_to_string(io::stdin())?
+	} else {
+		fs::read_to_string(path)?
+	};
+
+	let data = parse(&contents)?;
+
+	if (MIN_QUANTITY..=MAX_QUANTITY).contains(&data.len()) {
+		Ok(data)
+	} else {
+		Err(Error::QuantityOutOfRange(data.len()))
+	}
+}
+
+/* Parse whitespace or comma separated integers, or a JSON array of integers, each at most MAX_VALUE */
+fn parse(contents: &str) -> Result<Vec<u64>, Error> {
+	let contents = contents.trim();
+
+	let tokens: Vec<&str> = match contents.strip_prefix('[') {
+		Some(array) => {
+			let elements = array.strip_suffix(']')
+				.ok_or(Error::MalformedInput(String::from("JSON array is missing closing ']'")))?
+				.trim();
+
+			match elements {
+				"" => Vec::new(),
+				elements => elements.split(',').map(str::trim).collect(),
+			}
+		},
+		None => contents.split(|c: char| c.is_whitespace() || c == ',')
+			.filter(|token| !token.is_empty())
+			.collect(),
+	};
+
+	tokens.into_iter()
+		.map(|token| match token.parse::<u64>() {
+			Ok(value) if value > MAX_VALUE => Err(Error::ValueTooLarge(value)),
+			Ok(value) => Ok(value),
+			Err(_) => Err(Error::InvalidValue(String::from(token))),
+		})
+		.collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_whitespace_and_commas() {
+		assert_eq!(parse("3 1\n2\t5").unwrap(), vec![3, 1, 2, 5]);
+		assert_eq!(parse("3,1, 2 ,5,\n").unwrap(), vec![3, 1, 2, 5]);
+	}
+
+	#[test]
+	fn parses_json_array() {
+		assert_eq!(parse(" [3, 1,2 , 5]\n").unwrap(), vec![3, 1, 2, 5]);
+		assert_eq!(parse("[\n\t3,\n\t1\n]").unwrap(), vec![3, 1]);
+	}
+
+	#[test]
+	fn parses_empty_array() {
+		assert_eq!(parse("[]").unwrap(), Vec::<u64>::new());
+		assert_eq!(parse("[ \n ]").unwrap(), Vec::<u64>::new());
+	}
+
+	#[test]
+	fn rejects_trailing_comma_in_array() {
+		assert!(matches!(parse("[1, 2,]"), Err(Error::InvalidValue(token)) if token.is_empty()));
+	}
+
+	#[test]
+	fn rejects_unclosed_array() {
+		assert!(matches!(parse("[1, 2"), Err(Error::MalformedInput(_))));
+	}
+
+	#[test]
+	fn accepts_values_up_to_maximum() {
+		assert_eq!(parse(&format!("0 {}", MAX_VALUE)).unwrap(), vec![0, MAX_VALUE]);
+	}
+
+	#[test]
+	fn rejects_values_above_maximum() {
+		assert!(matches!(parse(&format!("1 {}", MAX_VALUE + 1)), Err(Error::ValueTooLarge(value)) if value == MAX_VALUE + 1));
+		assert!(matches!(parse(&format!("[1, {}]", u64::MAX)), Err(Error::ValueTooLarge(value)) if value == u64::MAX));
+	}
+
+	#[test]
+	fn rejects_negative_values() {
+		assert!(matches!(parse("1 -2 3"), Err(Error::InvalidValue(token)) if token == "-2"));
+		assert!(matches!(parse("[1, -2]"), Err(Error::InvalidValue(token)) if token == "-2"));
+	}
+}
@@ -1,13 +1,37 @@
-use clap::{CommandFactory, Parser};
-use sort::{Sort, SortSnapshot};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use event::SortEvent;
+use metrics::Metrics;
+use sort::Sort;
 use terminal::Terminal;
+use export::{Export, Format, Record};
+use distribution::Distribution;
+use gaps::Gaps;
+use race::Pace;
+use merge::Merge;
+use partition::{Partition, Pivot};
+use view::View;
+use rand::{rngs::StdRng, SeedableRng};
 
-use std::{fmt, io, time::Duration};
+use std::{fmt, io, path::PathBuf, time::Duration};
 
+mod event;
+mod history;
+mod stopwatch;
 mod sort;
 mod sort_type;
-mod count;
+mod metrics;
 mod analytics;
+mod distribution;
+mod gaps;
+mod merge;
+mod partition;
+mod pseudocode;
+mod input;
+mod bench;
+mod race;
+mod menu;
+mod export;
+mod view;
 mod terminal;
 
 use sort_type::SortType;
@@ -15,14 +39,33 @@ use sort_type::SortType;
 
 const MIN_QUANTITY: usize = 2;
 const DEFAULT_QUANTITY: usize = 50;
-const MAX_QUANTITY: usize = 150;
+const MAX_QUANTITY: usize = 5000;
 
+const DEFAULT_RUNS: usize = 100;
+
+/* Steps a bogosort may take when raced or benchmarked before it is abandoned, as it may never finish */
+const BOGO_STEP_LIMIT: usize = 50_000;
+
+/* Largest value accepted as input, so bar and dot heights can be scaled without overflowing */
+const MAX_VALUE: u64 = u32::MAX as u64;
+
+const DEFAULT_RADIX_BASE: u64 = 10;
+/* Radix sort allocates a bucket per digit, so the base is kept small */
+const MAX_RADIX_BASE: u64 = 1024;
+
+const MIN_TICK: u64 = 1;
 const DEFAULT_TICK: u64 = 100;
+const MAX_TICK: u64 = 5000;
 
 
+#[allow(clippy::enum_variant_names)]
 enum Error {
 	Interrupted,
 	QuantityOutOfRange(usize),
+	InvalidValue(String),
+	ValueTooLarge(u64),
+	MalformedInput(String),
+	RangeTooLarge(u64),
 	BarOverflow(usize),
 	IOError(io::Error),
 }
@@ -37,6 +80,10 @@ impl fmt::Debug for Error {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let written = writeln!(f, "{}", match self {
 			Error::Interrupted => String::from("Interrupted"),
+			Error::InvalidValue(token) => format!("input value '{}' is not a non-negative integer", token),
+			Error::ValueTooLarge(value) => format!("input value {} is larger than the maximum of {}", value, MAX_VALUE),
+			Error::MalformedInput(reason) => format!("input is malformed: {}", reason),
+			Error::RangeTooLarge(range) => format!("range of {} values is too large to count", range),
 			Error::BarOverflow(quantity) => format!("Terminal cannot render {} bars. Resize terminal or use smaller quantity", quantity),
 			Error::QuantityOutOfRange(quantity) => format!("quantity {} is not in range [{} - {}]", quantity, MIN_QUANTITY, MAX_QUANTITY),
 			Error::IOError(io_err) => io_err.to_string(),
@@ -53,52 +100,252 @@ impl fmt::Debug for Error {
 
 /// Sorts TUI: terminal interface for rendering and simulating sorting algorithms
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 struct Args {
-    /// Sort algorithm to use
-    sort_type: SortType,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-	/// Number of items to sort (2 - 150)
+    /// Sort algorithm to use, chosen from a menu if not given
+    sort_type: Option<SortType>,
+
+	/// Number of items to sort (2 - 5000)
     #[arg(short = 'n', long, default_value_t = DEFAULT_QUANTITY)]
 	quantity: usize,
 
-	/// How often interface reloads (in milliseconds)
-    #[arg(short, long, default_value_t = DEFAULT_TICK)]
+	/// How often interface reloads (in milliseconds, 1 - 5000)
+    #[arg(short, long, default_value_t = DEFAULT_TICK, value_parser = clap::value_parser!(u64).range(MIN_TICK..=MAX_TICK))]
     tick_rate: u64,
+
+	/// File of whitespace or comma separated integers, or a JSON array, to sort instead of generating data ("-" for stdin)
+	#[arg(short, long, conflicts_with = "quantity", requires = "sort_type")]
+	input: Option<PathBuf>,
+
+	/// Shape of generated data
+	#[arg(short, long, global = true, value_enum, default_value_t)]
+	distribution: Distribution,
+
+	/// Seed for generating data, random if not given
+	#[arg(short, long, global = true)]
+	seed: Option<u64>,
+
+	/// File to write results to (JSON unless extension is .csv)
+	#[arg(short, long, global = true)]
+	output: Option<PathBuf>,
+
+	/// Format of written results, printed to stdout if no output given
+	#[arg(short, long, global = true)]
+	format: Option<Format>,
+
+	/// How data is drawn, cycled with 'v' while sorting
+	#[arg(long, global = true, value_enum, default_value_t)]
+	view: View,
+
+	#[command(flatten)]
+	options: SortOptions,
+}
+
+/// Settings for algorithms with variants
+#[derive(clap::Args, Debug, Clone, Copy)]
+struct SortOptions {
+	/// Base of digits used by radix sort (2 - 1024)
+	#[arg(long, global = true, default_value_t = DEFAULT_RADIX_BASE, value_parser = clap::value_parser!(u64).range(2..=MAX_RADIX_BASE))]
+	radix_base: u64,
+
+	/// Gap sequence used by shell sort
+	#[arg(long, global = true, value_enum, default_value_t)]
+	gaps: Gaps,
+
+	/// How merge sort splits and merges its data
+	#[arg(long, global = true, value_enum, default_value_t)]
+	merge: Merge,
+
+	/// How quick sort chooses its pivot
+	#[arg(long, global = true, value_enum, default_value_t)]
+	pivot: Pivot,
+
+	/// How quick sort partitions around its pivot
+	#[arg(long, global = true, value_enum, default_value_t)]
+	partition: Partition,
+}
+
+impl SortOptions {
+	/* Description of the options used by sort type, if it has any */
+	fn variant(&self, sort_type: SortType) -> Option<String> {
+		match sort_type {
+			SortType::Shell => Some(cli_name(&self.gaps)),
+			SortType::Merge => Some(cli_name(&self.merge)),
+			SortType::Quick => Some(format!("{} pivot, {}", cli_name(&self.pivot), cli_name(&self.partition))),
+			SortType::Radix => Some(format!("base {}", self.radix_base)),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Run sorts without a terminal and print statistics
+	Bench(BenchArgs),
+	/// Run sorts side by side on the same data
+	Race(RaceArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct BenchArgs {
+	/// Sort algorithms to benchmark
+	#[arg(required = true)]
+	sort_types: Vec<SortType>,
+
+	/// Comma separated numbers of items to sort (2 - 5000)
+	#[arg(short = 'n', long = "quantity", value_delimiter = ',', default_values_t = [DEFAULT_QUANTITY])]
+	quantities: Vec<usize>,
+
+	/// Number of random inputs per algorithm and quantity
+	#[arg(short, long, default_value_t = DEFAULT_RUNS)]
+	runs: usize,
+}
+
+#[derive(clap::Args, Debug)]
+struct RaceArgs {
+	/// Sort algorithms to race
+	#[arg(required = true, num_args = 2..)]
+	sort_types: Vec<SortType>,
+
+	/// Number of items to sort (2 - 5000)
+	#[arg(short = 'n', long, default_value_t = DEFAULT_QUANTITY)]
+	quantity: usize,
+
+	/// How often interface reloads (in milliseconds, 1 - 5000)
+	#[arg(short, long, default_value_t = DEFAULT_TICK, value_parser = clap::value_parser!(u64).range(MIN_TICK..=MAX_TICK))]
+	tick_rate: u64,
+
+	/// How sorts are kept in step with each other
+	#[arg(short, long, value_enum, default_value_t)]
+	pace: Pace,
 }
 
 impl Args {
 	fn parse() -> Result<Args, Error> {
 		let args: Args = <Args as Parser>::parse();
+
+		let mut quantities = vec![args.quantity];
+		match &args.command {
+			Some(Command::Bench(bench_args)) => quantities.extend(&bench_args.quantities),
+			Some(Command::Race(race_args)) => quantities.push(race_args.quantity),
+			None => (),
+		}
 		
-		/* If quantity is valid return */
-		if (MIN_QUANTITY..=MAX_QUANTITY).contains(&args.quantity) {
-			Ok(args)
-		} else {
-			Err(Error::QuantityOutOfRange(args.quantity))
+		/* If all quantities are valid return */
+		match quantities.into_iter().find(|quantity| !(MIN_QUANTITY..=MAX_QUANTITY).contains(quantity)) {
+			Some(quantity) => Err(Error::QuantityOutOfRange(quantity)),
+			None => Ok(args),
+		}
+	}
+
+	/* Read data from input if given, otherwise generate it from seed */
+	fn data(&self, seed: u64) -> Result<Vec<u64>, Error> {
+		match &self.input {
+			Some(path) => input::read(path),
+			None => Ok(self.distribution.generate(self.quantity, &mut StdRng::seed_from_u64(seed))),
 		}
 	}
 }
 
 
-fn main() -> Result<(), Error> {    
-	let args = Args::parse()?;
-	let mut terminal = Terminal::new()?;
+fn main() -> Result<(), Error> {
+	/* Quitting early is not an error */
+	match run() {
+		Err(Error::Interrupted) => {
+			eprintln!("Quit before sorting finished");
+			Ok(())
+		},
+		result => result,
+	}
+}
+
+fn run() -> Result<(), Error> {
+	let mut args = Args::parse()?;
+	let export = Export::from_args(&args);
+
+	let seed = args.seed.unwrap_or_else(rand::random);
+
+	match args.command.take() {
+		Some(Command::Bench(bench_args)) => {
+			let stdout_taken = export.as_ref().is_some_and(Export::to_stdout);
+			let records = bench::run(bench_args, args.distribution, seed, args.options, stdout_taken);
+			eprintln!("Seed: {}", seed);
+			return export.map_or(Ok(()), |export| export.write(&records));
+		},
+		Some(Command::Race(race_args)) => {
+			let mut terminal = Terminal::new(args.view)?;
+			let result = race::run(&mut terminal, race_args, args.distribution, seed, args.options);
+
+			terminal.restore();
+			eprintln!("Seed: {}", seed);
+
+			let records = result?;
+			return export.map_or(Ok(()), |export| export.write(&records));
+		},
+		None => (),
+	}
 
-	let _count = Sort::from_args(&mut terminal, args).run()?;
+	let Some(sort_type) = args.sort_type else {
+		return menu::run(&args, export);
+	};
 
-	terminal.restore()?;
+	let data = args.data(seed)?;
+	let quantity = data.len();
+	let distribution = args.input.is_none().then_some(args.distribution);
+
+	let mut terminal = Terminal::new(args.view)?;
+	let result = visualise(&mut terminal, sort_type, data, args.tick_rate, seed, args.options);
+
+	terminal.restore();
+	eprintln!("Seed: {}", seed);
+
+	let (metrics, elapsed) = result?;
+
+	if let Some(export) = export {
+		export.write(&[Record::new(sort_type, args.options.variant(sort_type), quantity, distribution, seed, metrics, elapsed)])?;
+	}
 
 	Ok(())
 }
 
+/* Render sort of data in terminal, then scrub through its steps until quit, timing only the sort itself */
+fn visualise(terminal: &mut Terminal, sort_type: SortType, data: Vec<u64>, tick_rate: u64, seed: u64, options: SortOptions) -> Result<(Metrics, Duration), Error> {
+	let metrics = Sort::new(terminal, sort_type, data, tick_rate, seed, options).run()?;
+	let elapsed = terminal.get_elapsed();
+
+	terminal.finish()?;
+
+	Ok((metrics, elapsed))
+}
+
+/* Name of an option value used on the command line */
+pub fn cli_name<T: ValueEnum>(value: &T) -> String {
+	value.to_possible_value().map_or(String::new(), |value| value.get_name().to_string())
+}
+
+
+/* Decision made by the renderer after each tick */
+enum Control {
+	Continue,
+	Faster,
+	Slower,
+	Quit,
+}
 
 trait Renderer {
-	fn tick(&mut self, snapshot: SortSnapshot, duration: Duration) -> Result<(), Error> {
-		self.render(snapshot)?;
+	fn tick(&mut self, event: &SortEvent, duration: Duration) -> Result<Control, Error> {
+		self.render(event)?;
 		self.sleep(duration)
 	}
 	
-	fn render(&mut self, snapshot: SortSnapshot) -> Result<(), Error>;
-	fn sleep(&self, duration: Duration) -> Result<(), Error>;
+	fn render(&mut self, event: &SortEvent) -> Result<(), Error>;
+	fn sleep(&mut self, duration: Duration) -> Result<Control, Error>;
+
+	/* Called once the sort has finished */
+	fn finish(&mut self) -> Result<(), Error> {
+		Ok(())
+	}
 }
\ No newline at end of file
@@ -0,0 +1,164 @@
+use clap::ValueEnum;
+use rand::{rngs::StdRng, SeedableRng};
+use strum::IntoEnumIterator;
+
+use crate::{cli_name, distribution::Distribution, export::{Export, Record}, sort_type::SortType, terminal::Terminal, visualise, Args, Error, MAX_QUANTITY, MAX_TICK, MIN_QUANTITY, MIN_TICK};
+
+/* Least change in quantity per key press */
+const QUANTITY_STEP: usize = 10;
+
+/* Setting of the next run, changed from the menu */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Setting {
+	Quantity,
+	Distribution,
+	TickRate,
+}
+
+const SETTINGS: [Setting; 3] = [Setting::Quantity, Setting::Distribution, Setting::TickRate];
+
+impl Setting {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Setting::Quantity => "Quantity",
+			Setting::Distribution => "Distribution",
+			Setting::TickRate => "Tick rate",
+		}
+	}
+}
+
+
+/* Algorithm and settings chosen on the launch menu */
+pub struct Menu {
+	sort_types: Vec<SortType>,
+	selected: usize,
+	setting: usize,
+	quantity: usize,
+	distribution: Distribution,
+	tick_rate: u64,
+}
+
+impl Menu {
+	/* Menu starting from the settings given as arguments */
+	pub fn new(args: &Args) -> Menu {
+		Menu {
+			sort_types: SortType::iter().collect(),
+			selected: 0,
+			setting: 0,
+			quantity: args.quantity,
+			distribution: args.distribution,
+			tick_rate: args.tick_rate,
+		}
+	}
+
+	/* Move selected algorithm up or down the list, wrapping around */
+	pub fn select(&mut self, down: bool) {
+		let len = self.sort_types.len();
+		self.selected = if down { (self.selected + 1) % len } else { (self.selected + len - 1) % len };
+	}
+
+	/* Focus next or previous setting, wrapping around */
+	pub fn focus(&mut self, next: bool) {
+		let len = SETTINGS.len();
+		self.setting = if next { (self.setting + 1) % len } else { (self.setting + len - 1) % len };
+	}
+
+	/* Increase or decrease focused setting */
+	pub fn adjust(&mut self, increase: bool) {
+		match SETTINGS[self.setting] {
+			Setting::Quantity => {
+				self.quantity = if increase {
+					self.quantity + quantity_step(self.quantity)
+				} else {
+					self.quantity.saturating_sub(quantity_step(self.quantity - 1))
+				}.clamp(MIN_QUANTITY, MAX_QUANTITY);
+			},
+			Setting::Distribution => {
+				let distributions = Distribution::value_variants();
+				let len = distributions.len();
+				let index = distributions.iter()
+					.position(|distribution| *distribution == self.distribution)
+					.unwrap_or(0);
+
+				self.distribution = distributions[if increase { (index + 1) % len } else { (index + len - 1) % len }];
+			},
+			/* Faster is a shorter tick */
+			Setting::TickRate => {
+				self.tick_rate = if increase { self.tick_rate * 2 } else { self.tick_rate / 2 }.clamp(MIN_TICK, MAX_TICK);
+			},
+		}
+	}
+
+	pub fn get_sort_types(&self) -> &Vec<SortType> {
+		&self.sort_types
+	}
+
+	pub fn get_selected(&self) -> usize {
+		self.selected
+	}
+
+	pub fn get_sort_type(&self) -> SortType {
+		self.sort_types[self.selected]
+	}
+
+	/* Each setting with its current value, and whether it is focused */
+	pub fn get_settings(&self) -> Vec<(Setting, String, bool)> {
+		SETTINGS.iter()
+			.enumerate()
+			.map(|(index, setting)| {
+				let value = match setting {
+					Setting::Quantity => self.quantity.to_string(),
+					Setting::Distribution => cli_name(&self.distribution),
+					Setting::TickRate => format!("{} ms", self.tick_rate),
+				};
+
+				(*setting, value, index == self.setting)
+			})
+			.collect()
+	}
+}
+
+
+/* Quantity changes by the place value of its leading digit, so large quantities are quick to reach */
+fn quantity_step(quantity: usize) -> usize {
+	10_usize.pow(quantity.max(1).ilog10()).max(QUANTITY_STEP)
+}
+
+
+/* Show menu until quit, running each chosen sort and returning to the menu once it has finished */
+pub fn run(args: &Args, export: Option<Export>) -> Result<(), Error> {
+	let mut terminal = Terminal::new(args.view)?;
+	let mut menu = Menu::new(args);
+	let mut records = Vec::new();
+	let mut seeds = Vec::new();
+
+	let result = loop {
+		match terminal.menu(&mut menu) {
+			Ok(true) => (),
+			Ok(false) => break Ok(()),
+			Err(error) => break Err(error),
+		}
+
+		let sort_type = menu.get_sort_type();
+		let seed = args.seed.unwrap_or_else(rand::random);
+		let data = menu.distribution.generate(menu.quantity, &mut StdRng::seed_from_u64(seed));
+		seeds.push(seed);
+
+		/* Quitting a sort returns to the menu */
+		match visualise(&mut terminal, sort_type, data, menu.tick_rate, seed, args.options) {
+			Ok((metrics, elapsed)) => records.push(Record::new(sort_type, args.options.variant(sort_type), menu.quantity, Some(menu.distribution), seed, metrics, elapsed)),
+			Err(Error::Interrupted) => (),
+			Err(error) => break Err(error),
+		}
+
+		terminal.reset();
+	};
+
+	terminal.restore();
+	for seed in seeds {
+		eprintln!("Seed: {}", seed);
+	}
+
+	result?;
+	export.map_or(Ok(()), |export| export.write(&records))
+}
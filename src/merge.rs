@@ -0,0 +1,13 @@
+use clap::ValueEnum;
+
+/* How merge sort splits and merges its data */
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum Merge {
+	/* Recursively halve, merging through copies of both halves */
+	#[default]
+	TopDown,
+	/* Merge runs of doubling width, merging through copies of both runs */
+	BottomUp,
+	/* Recursively halve, merging by rotating values without copies */
+	InPlace,
+}
@@ -0,0 +1,168 @@
+use std::fmt::{self, Display};
+
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{event::SortEvent, sort_type::SortType, SortOptions};
+
+/* Quantity counted by metrics */
+#[derive(EnumIter, Debug, Clone, Copy, PartialEq)]
+pub enum Counter {
+    Comparisons,
+    Passes,
+    Swaps,
+    Writes,
+    Reads,
+    Auxiliary,
+    PeakAuxiliary,
+    MaxDepth,
+    Steps,
+}
+
+impl Counter {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Counter::Comparisons => "Comparisons",
+            Counter::Passes => "Passes",
+            Counter::Swaps => "Swaps",
+            Counter::Writes => "Writes",
+            Counter::Reads => "Reads",
+            Counter::Auxiliary => "Auxiliary",
+            Counter::PeakAuxiliary => "Peak Auxiliary",
+            Counter::MaxDepth => "Max Depth",
+            Counter::Steps => "Steps",
+        }
+    }
+}
+
+
+/* Operation counts of a sort, where reads and writes include auxiliary arrays */
+#[derive(Clone, Default)]
+pub struct Metrics {
+    comparisons: usize,
+    passes: Option<usize>,
+    swaps: usize,
+    writes: usize,
+    reads: usize,
+    allocations: usize,
+    allocated: usize,
+    peak_allocated: usize,
+    depth: usize,
+    max_depth: Option<usize>,
+    steps: usize,
+}
+
+impl Metrics {
+    /* Metrics for sort type run with options, counting digit passes if it doesn't compare and recursion depth if it recurses */
+    pub fn new(sort_type: SortType, options: &SortOptions) -> Metrics {
+        Metrics {
+            passes: (!sort_type.is_comparison()).then_some(0),
+            max_depth: sort_type.is_divide_and_conquer(options).then_some(0),
+            ..Metrics::default()
+        }
+    }
+
+    /* Count the operations performed by event */
+    pub fn record(&mut self, event: &SortEvent) {
+        if !matches!(event, SortEvent::Start { .. } | SortEvent::Trace { .. }) {
+            self.steps += 1;
+        }
+
+        match event {
+            SortEvent::Compare(..) => {
+                self.comparisons += 1;
+                self.reads += 2;
+            },
+            SortEvent::Read(_) => self.reads += 1,
+            SortEvent::Swap(..) => self.swap(1),
+            SortEvent::Write { .. } | SortEvent::Restore { .. } => {
+                self.reads += 1;
+                self.writes += 1;
+            },
+            SortEvent::Allocate(start, end) => {
+                self.allocate(end - start);
+                self.reads += end - start;
+                self.writes += end - start;
+            },
+            SortEvent::Free(start, end) => self.allocated -= end - start,
+            SortEvent::Buckets { buckets, capacity } => self.allocate(buckets + capacity),
+            SortEvent::Pass(_) => *self.passes.get_or_insert(0) += 1,
+            SortEvent::EnterRange(..) => {
+                self.depth += 1;
+                self.max_depth = Some(self.max_depth.unwrap_or(0).max(self.depth));
+            },
+            SortEvent::LeaveRange(..) => self.depth = self.depth.saturating_sub(1),
+            SortEvent::Bucket { .. } | SortEvent::Drain { .. } => {
+                self.reads += 1;
+                self.writes += 1;
+            },
+            /* Fisher-Yates performs one swap per element after the first */
+            SortEvent::Shuffle(data) => self.swap(data.len().saturating_sub(1)),
+            _ => (),
+        }
+    }
+
+    fn allocate(&mut self, size: usize) {
+        self.allocations += size;
+        self.allocated += size;
+        self.peak_allocated = self.peak_allocated.max(self.allocated);
+    }
+
+    fn swap(&mut self, swaps: usize) {
+        self.swaps += swaps;
+        self.reads += 2 * swaps;
+        self.writes += 2 * swaps;
+    }
+
+    /* Each counter and its value, with digit passes in place of comparisons and recursion depth if counted */
+    pub fn counters(&self) -> Vec<(Counter, usize)> {
+        self.all_counters().into_iter()
+            .filter(|(counter, _)| match counter {
+                Counter::Comparisons => self.passes.is_none(),
+                Counter::Passes => self.passes.is_some(),
+                Counter::MaxDepth => self.max_depth.is_some(),
+                _ => true,
+            })
+            .collect()
+    }
+
+    /* Every counter and its value */
+    pub fn all_counters(&self) -> Vec<(Counter, usize)> {
+        Counter::iter().map(|counter| (counter, self.get(counter))).collect()
+    }
+
+    /* Value of counter, zero if not counted for this sort */
+    pub fn get(&self, counter: Counter) -> usize {
+        match counter {
+            Counter::Comparisons => self.comparisons,
+            Counter::Passes => self.passes.unwrap_or(0),
+            Counter::Swaps => self.swaps,
+            Counter::Writes => self.writes,
+            Counter::Reads => self.reads,
+            Counter::Auxiliary => self.allocations,
+            Counter::PeakAuxiliary => self.peak_allocated,
+            Counter::MaxDepth => self.max_depth.unwrap_or(0),
+            Counter::Steps => self.steps,
+        }
+    }
+
+    /* Number of events the sort took */
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /* Counters on a single line */
+    pub fn summary(&self) -> String {
+        self.to_string().lines().collect::<Vec<&str>>().join("  ")
+    }
+}
+
+impl Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (counter, value) in self.counters() {
+            writeln!(f, "{}: {}", counter.name(), value)?;
+        }
+
+        Ok(())
+    }
+}
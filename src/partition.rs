@@ -0,0 +1,22 @@
+use clap::ValueEnum;
+
+/* How quick sort chooses the pivot of each range */
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum Pivot {
+	First,
+	#[default]
+	Last,
+	Middle,
+	Random,
+	MedianOfThree,
+	Ninther,
+}
+
+/* How quick sort divides each range around the pivot */
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum Partition {
+	#[default]
+	Lomuto,
+	Hoare,
+	ThreeWay,
+}
@@ -0,0 +1,244 @@
+use crate::{merge::Merge, partition::Partition, sort_type::SortType, SortOptions};
+
+/* Listings are numbered from 1, matching the lines traced by each algorithm in sort.rs */
+
+const BOGO: &[&str] = &[
+	"while data is not sorted:",
+	"    shuffle data",
+];
+
+const BUBBLE: &[&str] = &[
+	"for i in 0 .. n - 1:",
+	"    swapped ← false",
+	"    for j in 0 .. n - i - 1:",
+	"        if data[j] > data[j + 1]:",
+	"            swap data[j], data[j + 1]",
+	"            swapped ← true",
+	"    if not swapped:",
+	"        break",
+];
+
+const INSERTION: &[&str] = &[
+	"for i in 1 .. n:",
+	"    key ← data[i]",
+	"    j ← i",
+	"    while j > 0 and data[j - 1] > key:",
+	"        data[j] ← data[j - 1]",
+	"        j ← j - 1",
+	"    data[j] ← key",
+];
+
+const SHELL: &[&str] = &[
+	"for gap in gaps smaller than n:",
+	"    for i in gap .. n:",
+	"        j ← i",
+	"        while j ≥ gap and data[j - gap] > data[j]:",
+	"            swap data[j - gap], data[j]",
+	"            j ← j - gap",
+];
+
+const TOP_DOWN_MERGE: &[&str] = &[
+	"merge_sort(left, right):",
+	"    if right - left > 1:",
+	"        mid ← (left + right) / 2",
+	"        merge_sort(left, mid)",
+	"        merge_sort(mid, right)",
+	"        merge(left, mid, right)",
+	"",
+	"merge(left, mid, right):",
+	"    L ← data[left..mid], R ← data[mid..right]",
+	"    i ← 0, j ← 0, k ← left",
+	"    while i < |L| and j < |R|:",
+	"        if L[i] ≤ R[j]:",
+	"            data[k] ← L[i], i ← i + 1",
+	"        else:",
+	"            data[k] ← R[j], j ← j + 1",
+	"        k ← k + 1",
+	"    copy rest of L and R to data[k..right]",
+];
+
+const BOTTOM_UP_MERGE: &[&str] = &[
+	"width ← 1",
+	"while width < n:",
+	"    for left in 0 .. n step 2 × width:",
+	"        mid ← left + width",
+	"        merge(left, mid, min(mid + width, n))",
+	"    width ← 2 × width",
+	"",
+	"merge(left, mid, right):",
+	"    L ← data[left..mid], R ← data[mid..right]",
+	"    i ← 0, j ← 0, k ← left",
+	"    while i < |L| and j < |R|:",
+	"        if L[i] ≤ R[j]:",
+	"            data[k] ← L[i], i ← i + 1",
+	"        else:",
+	"            data[k] ← R[j], j ← j + 1",
+	"        k ← k + 1",
+	"    copy rest of L and R to data[k..right]",
+];
+
+const IN_PLACE_MERGE: &[&str] = &[
+	"merge_sort(left, right):",
+	"    if right - left > 1:",
+	"        mid ← (left + right) / 2",
+	"        merge_sort(left, mid)",
+	"        merge_sort(mid, right)",
+	"        merge(left, mid, right)",
+	"",
+	"merge(left, mid, right):",
+	"    if left = mid or mid = right: return",
+	"    if right - left = 2:",
+	"        if data[mid] < data[left]: swap them",
+	"        return",
+	"    cut longer half in the middle",
+	"    search other half for where its value goes",
+	"    rotate data[left_cut..right_cut] to swap cuts",
+	"    merge(left, left_cut, new_mid)",
+	"    merge(new_mid, right_cut, right)",
+];
+
+const TIM: &[&str] = &[
+	"min_run ← min_run(n)",
+	"for each run from start:",
+	"    end ← end of run, reversed if descending",
+	"    if end - start < min_run:",
+	"        insertion sort to extend run to min_run",
+	"    push run onto stack",
+	"    while top runs of stack are unbalanced:",
+	"        merge two of the top runs",
+	"merge all runs left on stack",
+	"",
+	"merge(left, mid, right):",
+	"    skip values already in place at both ends",
+	"    L ← data[left..mid]",
+	"    while values of L and right run remain:",
+	"        if one run won 7 times in a row:",
+	"            gallop to copy a block of it at once",
+	"        else if data[j] < L[i]:",
+	"            data[k] ← data[j], j ← j + 1",
+	"        else:",
+	"            data[k] ← L[i], i ← i + 1",
+	"    copy rest of L to data[k..right]",
+];
+
+const LOMUTO_QUICK: &[&str] = &[
+	"quick_sort(start, end):",
+	"    if start < end:",
+	"        pivot ← choose_pivot(start, end)",
+	"        left, right ← partition(start, end, pivot)",
+	"        quick_sort(start, left)",
+	"        quick_sort(right, end)",
+	"",
+	"partition(start, end, pivot):",
+	"    swap data[pivot], data[end - 1]",
+	"    i ← start",
+	"    for j in start .. end - 1:",
+	"        if data[j] ≤ data[end - 1]:",
+	"            swap data[i], data[j]",
+	"            i ← i + 1",
+	"    swap data[i], data[end - 1]",
+	"    return i, i + 1",
+];
+
+const HOARE_QUICK: &[&str] = &[
+	"quick_sort(start, end):",
+	"    if start < end:",
+	"        pivot ← choose_pivot(start, end)",
+	"        left, right ← partition(start, end, pivot)",
+	"        quick_sort(start, left)",
+	"        quick_sort(right, end)",
+	"",
+	"partition(start, end, pivot):",
+	"    swap data[pivot], data[start]",
+	"    i ← start, j ← end",
+	"    loop:",
+	"        do i ← i + 1 while data[i] < data[start]",
+	"        do j ← j - 1 while data[j] > data[start]",
+	"        if i ≥ j: break",
+	"        swap data[i], data[j]",
+	"    swap data[start], data[j]",
+	"    return j, j + 1",
+];
+
+const THREE_WAY_QUICK: &[&str] = &[
+	"quick_sort(start, end):",
+	"    if start < end:",
+	"        pivot ← choose_pivot(start, end)",
+	"        left, right ← partition(start, end, pivot)",
+	"        quick_sort(start, left)",
+	"        quick_sort(right, end)",
+	"",
+	"partition(start, end, pivot):",
+	"    swap data[pivot], data[start]",
+	"    lt ← start, i ← start + 1, gt ← end",
+	"    while i < gt:",
+	"        if data[i] < data[lt]:",
+	"            swap data[lt], data[i]",
+	"            lt ← lt + 1, i ← i + 1",
+	"        else if data[i] > data[lt]:",
+	"            gt ← gt - 1, swap data[i], data[gt]",
+	"        else: i ← i + 1",
+	"    return lt, gt",
+];
+
+const HEAP: &[&str] = &[
+	"for root in n / 2 - 1 down to 0:",
+	"    sift_down(root, n)",
+	"for end in n - 1 down to 1:",
+	"    swap data[0], data[end]",
+	"    sift_down(0, end)",
+	"",
+	"sift_down(root, end):",
+	"    loop:",
+	"        largest ← largest of root and children",
+	"        if largest = root: return",
+	"        swap data[root], data[largest]",
+	"        root ← largest",
+];
+
+const COUNTING: &[&str] = &[
+	"min, max ← smallest and largest values",
+	"buckets ← a bucket for each of min ..= max",
+	"for i in 0 .. n:",
+	"    append data[i] to buckets[data[i] - min]",
+	"k ← 0",
+	"for each bucket in order:",
+	"    for each value in bucket:",
+	"        data[k] ← value, k ← k + 1",
+];
+
+const RADIX: &[&str] = &[
+	"max ← largest value",
+	"buckets ← a bucket for each digit",
+	"for each digit place of max, least significant first:",
+	"    for i in 0 .. n:",
+	"        append data[i] to bucket of its digit",
+	"    k ← 0",
+	"    for each bucket in order:",
+	"        for each value in bucket:",
+	"            data[k] ← value, k ← k + 1",
+];
+
+/* Pseudocode of sort type when run with options */
+pub fn listing(sort_type: SortType, options: &SortOptions) -> &'static [&'static str] {
+	match sort_type {
+		SortType::Bogo => BOGO,
+		SortType::Bubble => BUBBLE,
+		SortType::Insertion => INSERTION,
+		SortType::Shell => SHELL,
+		SortType::Merge => match options.merge {
+			Merge::TopDown => TOP_DOWN_MERGE,
+			Merge::BottomUp => BOTTOM_UP_MERGE,
+			Merge::InPlace => IN_PLACE_MERGE,
+		},
+		SortType::Tim => TIM,
+		SortType::Quick => match options.partition {
+			Partition::Lomuto => LOMUTO_QUICK,
+			Partition::Hoare => HOARE_QUICK,
+			Partition::ThreeWay => THREE_WAY_QUICK,
+		},
+		SortType::Heap => HEAP,
+		SortType::Counting => COUNTING,
+		SortType::Radix => RADIX,
+	}
+}
@@ -0,0 +1,276 @@
+use std::{sync::mpsc::{self, Receiver, Sender}, thread::{self, JoinHandle}, time::Duration};
+
+use clap::ValueEnum;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{distribution::Distribution, event::SortEvent, export::Record, metrics::Metrics, sort::{Sort, SortSnapshot}, sort_type::SortType, stopwatch::Stopwatch, terminal::Terminal, Control, Error, RaceArgs, Renderer, SortOptions, BOGO_STEP_LIMIT, MAX_TICK, MIN_TICK};
+
+/* How racing sorts are kept in lockstep */
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum Pace {
+	/* Every sort makes one comparison per tick, or one step if it doesn't compare */
+	#[default]
+	Comparisons,
+	/* Every sort performs one step per tick */
+	Steps,
+	/* Every sort advances by the same share of wall clock time per tick */
+	Time,
+}
+
+/* Progress of a sort through the race */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Standing {
+	Racing,
+	Placed(usize),
+	DidNotFinish,
+}
+
+
+/* Progress of a racing sort, sent whenever it has used up its tick */
+struct Report {
+	snapshot: SortSnapshot,
+	elapsed: Duration,
+	/* Set once the sort stops, to whether it sorted the data */
+	completed: Option<bool>,
+}
+
+/* Renderer driving a sort on its own thread, pausing it after each tick until the race advances */
+struct Lane {
+	pace: Pace,
+	snapshot: Option<SortSnapshot>,
+	stopwatch: Stopwatch,
+	/* Time spent sorting the current tick may run up to when pacing by wall clock */
+	deadline: Duration,
+	spent: bool,
+	reports: Sender<Report>,
+	ticks: Receiver<Duration>,
+}
+
+impl Lane {
+	/* Send progress to the race, returning the deadline of the next tick or None once the race is over */
+	fn report(&mut self, completed: Option<bool>) -> Option<Duration> {
+		let snapshot = self.snapshot.clone()?;
+		let report = Report { snapshot, elapsed: self.stopwatch.get_elapsed(), completed };
+
+		self.reports.send(report).ok()?;
+		self.ticks.recv().ok()
+	}
+}
+
+impl Renderer for Lane {
+	fn render(&mut self, event: &SortEvent) -> Result<(), Error> {
+		/* Time spent rendering and waiting for the race is excluded */
+		match event {
+			SortEvent::Start { .. } => self.stopwatch = Stopwatch::new(),
+			_ => self.stopwatch.pause(),
+		}
+
+		match &mut self.snapshot {
+			Some(snapshot) => snapshot.apply(event),
+			None => self.snapshot = SortSnapshot::from_event(event),
+		}
+
+		let comparison = self.snapshot.as_ref().is_some_and(|snapshot| snapshot.get_sort_type().is_comparison());
+
+		self.spent |= match (event, self.pace) {
+			/* Racers are shown as soon as they start and once they finish */
+			(SortEvent::Start { .. } | SortEvent::Finish, _) => true,
+			/* Pseudocode traces are not steps, and are followed by no sleep */
+			(SortEvent::Trace { .. }, _) => {
+				self.stopwatch.resume();
+				false
+			},
+			(SortEvent::Compare(..), Pace::Comparisons) | (_, Pace::Steps) => true,
+			(_, Pace::Comparisons) => !comparison,
+			(_, Pace::Time) => false,
+		};
+
+		Ok(())
+	}
+
+	fn sleep(&mut self, _duration: Duration) -> Result<Control, Error> {
+		let Some(snapshot) = &self.snapshot else {
+			return Ok(Control::Continue);
+		};
+
+		let finished = snapshot.is_finished();
+		let abandoned = matches!(snapshot.get_sort_type(), SortType::Bogo) && snapshot.get_metrics().steps() >= BOGO_STEP_LIMIT;
+
+		if abandoned {
+			self.report(Some(false));
+			return Ok(Control::Quit);
+		}
+
+		if self.spent || self.stopwatch.get_elapsed() >= self.deadline {
+			self.spent = false;
+
+			match self.report(finished.then_some(true)) {
+				Some(deadline) => self.deadline = deadline,
+				None => return Ok(Control::Quit),
+			}
+		}
+
+		self.stopwatch.resume();
+		Ok(Control::Continue)
+	}
+}
+
+
+/* Sort running in the race, as last reported by its lane */
+struct Racer {
+	sort_type: SortType,
+	snapshot: SortSnapshot,
+	elapsed: Duration,
+	standing: Standing,
+	completed: bool,
+	ticks: Sender<Duration>,
+	reports: Receiver<Report>,
+	thread: Option<JoinHandle<Result<Metrics, Error>>>,
+}
+
+impl Racer {
+	/* Start sorting data on its own thread, stopping bogosort at the step limit, and wait for it to start */
+	fn start(sort_type: SortType, data: Vec<u64>, seed: u64, options: SortOptions, pace: Pace) -> Result<Racer, Error> {
+		let (report_sender, reports) = mpsc::channel();
+		let (ticks, tick_receiver) = mpsc::channel();
+
+		let thread = thread::spawn(move || {
+			let mut lane = Lane {
+				pace,
+				snapshot: None,
+				stopwatch: Stopwatch::new(),
+				deadline: Duration::ZERO,
+				spent: false,
+				reports: report_sender,
+				ticks: tick_receiver,
+			};
+
+			Sort::new(&mut lane, sort_type, data, 0, seed, options).run()
+		});
+
+		let report = match reports.recv() {
+			Ok(report) => report,
+			Err(_) => return Err(Racer::failure(thread)),
+		};
+
+		Ok(Racer {
+			sort_type,
+			snapshot: report.snapshot,
+			elapsed: report.elapsed,
+			standing: Standing::Racing,
+			completed: false,
+			ticks,
+			reports,
+			thread: Some(thread),
+		})
+	}
+
+	/* Error that stopped a sort before it reported back */
+	fn failure(thread: JoinHandle<Result<Metrics, Error>>) -> Error {
+		match thread.join() {
+			Ok(Err(error)) => error,
+			Ok(Ok(_)) => Error::Interrupted,
+			Err(panic) => std::panic::resume_unwind(panic),
+		}
+	}
+
+	/* Let sort use up one tick of the pace, up to deadline of sorting time if pacing by wall clock, returning whether it stopped */
+	fn advance(&mut self, deadline: Duration) -> Result<Option<bool>, Error> {
+		/* A lane that has stopped fails to receive, and its error is collected below */
+		let _ = self.ticks.send(deadline);
+
+		let report = match self.reports.recv() {
+			Ok(report) => report,
+			Err(_) => return Err(self.thread.take().map_or(Error::Interrupted, Racer::failure)),
+		};
+
+		self.snapshot = report.snapshot;
+		self.elapsed = report.elapsed;
+
+		Ok(report.completed)
+	}
+
+	/* Mean sorting time of the steps taken so far */
+	fn step_time(&self) -> Duration {
+		self.elapsed / self.snapshot.get_metrics().steps().max(1) as u32
+	}
+}
+
+
+/* Race sort types on the same generated data, showing each in a grid until quit */
+pub fn run(terminal: &mut Terminal, args: RaceArgs, distribution: Distribution, seed: u64, options: SortOptions) -> Result<Vec<Record>, Error> {
+	let data = distribution.generate(args.quantity, &mut StdRng::seed_from_u64(seed));
+
+	let mut racers = args.sort_types.iter()
+		.map(|sort_type| Racer::start(*sort_type, data.clone(), seed, options, args.pace))
+		.collect::<Result<Vec<Racer>, Error>>()?;
+
+	let mut tick_rate = args.tick_rate;
+	let mut time = Duration::ZERO;
+	let mut finishers = 0;
+
+	show(terminal, &racers)?;
+
+	while racers.iter().any(|racer| racer.standing == Standing::Racing) {
+		let mut control = terminal.sleep(Duration::from_millis(tick_rate))?;
+
+		loop {
+			match control {
+				Control::Continue => break,
+				Control::Quit => return Err(Error::Interrupted),
+				Control::Faster => tick_rate = (tick_rate / 2).clamp(MIN_TICK, MAX_TICK),
+				Control::Slower => tick_rate = (tick_rate * 2).clamp(MIN_TICK, MAX_TICK),
+			}
+
+			control = terminal.sleep(Duration::from_millis(tick_rate))?;
+		}
+
+		/* Wall clock advanced per tick, so the slowest stepping sort takes about one step per tick */
+		time += racers.iter()
+			.filter(|racer| racer.standing == Standing::Racing)
+			.map(Racer::step_time)
+			.max()
+			.unwrap_or_default();
+
+		/* Sorts finishing on the same tick share a place */
+		let place = finishers + 1;
+
+		for racer in racers.iter_mut().filter(|racer| racer.standing == Standing::Racing) {
+			/* Sorts that didn't finish take no place */
+			match racer.advance(time)? {
+				Some(true) => {
+					racer.standing = Standing::Placed(place);
+					racer.completed = true;
+					finishers += 1;
+				},
+				Some(false) => racer.standing = Standing::DidNotFinish,
+				None => (),
+			}
+		}
+
+		show(terminal, &racers)?;
+	}
+
+	terminal.wait()?;
+
+	Ok(racers.iter()
+		.map(|racer| Record::new(
+			racer.sort_type,
+			options.variant(racer.sort_type),
+			args.quantity,
+			Some(distribution),
+			seed,
+			racer.snapshot.get_metrics().clone(),
+			racer.elapsed,
+		).finished(racer.completed))
+		.collect())
+}
+
+/* Show current snapshot and standing of each racer */
+fn show(terminal: &mut Terminal, racers: &[Racer]) -> Result<(), Error> {
+	let lanes = racers.iter()
+		.map(|racer| (racer.snapshot.clone(), racer.standing))
+		.collect();
+
+	terminal.show_race(lanes)
+}
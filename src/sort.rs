@@ -1,129 +1,493 @@
-use std::time::Duration;
-use rand::{seq::SliceRandom, thread_rng};
+use std::{cmp::Ordering, collections::VecDeque, time::Duration};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 
-use crate::{count::Count, sort_type::SortType, Args, Error, Renderer};
+use crate::{event::SortEvent, sort_type::SortType, metrics::Metrics, merge::Merge, partition::{Partition, Pivot}, pseudocode, Control, Error, Renderer, SortOptions, MAX_TICK, MIN_TICK};
 
+/* Largest range of values counting sort will allocate buckets for */
+const MAX_COUNTING_RANGE: u64 = 1 << 16;
+
+/* Below this length timsort extends runs with insertion, smaller than the usual 64 so runs are visible */
+const MIN_MERGE: usize = 16;
+/* Consecutive wins by one run before timsort switches to galloping */
+const MIN_GALLOP: usize = 7;
+
+/* Ranges at least this long take the ninther as pivot, shorter ones the median of three */
+const NINTHER_MIN: usize = 40;
+
+/* Mixed into the seed for shuffles and random pivots, so they don't repeat the stream that generated the data */
+const SORT_SEED_MIX: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/* Role of an index in the last operation */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Highlight {
+    Compared,
+    Changed,
+    Pivot,
+}
+
+/* Auxiliary copy of part of the data, read from the cursor onwards */
+#[derive(Clone)]
+pub struct Buffer {
+    start: usize,
+    values: Vec<u64>,
+    cursor: usize,
+}
+
+impl Buffer {
+    /* Check if buffer holds a copy of data[index] */
+    pub fn covers(&self, index: usize) -> bool {
+        (self.start .. self.start + self.values.len()).contains(&index)
+    }
+
+    /* Index in data the buffer was copied from */
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    pub fn get_values(&self) -> &Vec<u64> {
+        &self.values
+    }
+
+    /* Offset of the value being read, or the next to be read */
+    pub fn get_cursor(&self) -> usize {
+        self.cursor
+    }
+}
+
+#[derive(Clone)]
 pub struct SortSnapshot {
-    data: Vec<u64>,    
-    is_sorted: bool,
+    data: Vec<u64>,
     sort_type: SortType,
-    count: Count,
+    options: SortOptions,
+    seed: u64,
+    metrics: Metrics,
+    highlights: Vec<(usize, Highlight)>,
+    buffers: Vec<Buffer>,
+    buffer_highlights: Vec<(usize, Highlight)>,
+    uses_buffers: bool,
+    stack: Vec<(usize, usize)>,
+    pivot: Option<usize>,
+    gap: Option<usize>,
+    chain: Option<usize>,
+    runs: Vec<(usize, usize)>,
+    line: Option<usize>,
+    vars: Vec<(&'static str, usize)>,
+    heap: Option<usize>,
+    buckets: Vec<VecDeque<u64>>,
+    bucket: Option<usize>,
+    pass: usize,
+    finished: bool,
 }
 
 impl SortSnapshot {
+    pub fn new(sort_type: SortType, options: SortOptions, seed: u64, data: Vec<u64>) -> SortSnapshot {
+        SortSnapshot {
+            data,
+            sort_type,
+            options,
+            seed,
+            metrics: Metrics::new(sort_type, &options),
+            highlights: Vec::new(),
+            buffers: Vec::new(),
+            buffer_highlights: Vec::new(),
+            uses_buffers: false,
+            stack: Vec::new(),
+            pivot: None,
+            gap: None,
+            chain: None,
+            runs: Vec::new(),
+            line: None,
+            vars: Vec::new(),
+            heap: None,
+            buckets: Vec::new(),
+            bucket: None,
+            pass: 0,
+            finished: false,
+        }
+    }
+
+    /* Reconstruct snapshot from the start event */
+    pub fn from_event(event: &SortEvent) -> Option<SortSnapshot> {
+        match event {
+            SortEvent::Start { sort_type, options, seed, data } => Some(SortSnapshot::new(*sort_type, *options, *seed, data.clone())),
+            _ => None,
+        }
+    }
+
+    /* Update snapshot with the effect of an event */
+    pub fn apply(&mut self, event: &SortEvent) {
+        /* Tracing keeps the highlights of the last operation */
+        if let SortEvent::Trace { line, vars } = event {
+            self.line = Some(*line);
+            self.vars.clone_from(vars);
+            return;
+        }
+
+        self.highlights.clear();
+        self.buffer_highlights.clear();
+        self.bucket = None;
+
+        match event {
+            SortEvent::Start { .. } => *self = SortSnapshot::from_event(event).unwrap(),
+            SortEvent::Compare(i, j) => {
+                self.chain = self.gap.map(|gap| j % gap);
+                self.read(*i);
+                self.read(*j);
+            },
+            SortEvent::Read(index) => self.read(*index),
+            SortEvent::Swap(i, j) => {
+                self.data.swap(*i, *j);
+                self.highlights.extend([(*i, Highlight::Changed), (*j, Highlight::Changed)]);
+
+                /* Pivot moves with its value */
+                self.pivot = self.pivot.map(|p| match p {
+                    p if p == *i => *j,
+                    p if p == *j => *i,
+                    p => p,
+                });
+            },
+            SortEvent::Write { index, value } => {
+                self.data[*index] = *value;
+                self.highlights.push((*index, Highlight::Changed));
+            },
+            SortEvent::Pivot(index) => self.pivot = Some(*index),
+            SortEvent::Gap(gap) => {
+                self.gap = Some(*gap);
+                self.chain = None;
+            },
+            SortEvent::Run(start, end) => {
+                self.runs.retain(|(s, e)| e <= start || s >= end);
+                self.runs.push((*start, *end));
+                self.runs.sort_unstable();
+            },
+            SortEvent::Heap(end) => self.heap = Some(*end),
+            SortEvent::Buckets { buckets, .. } => self.buckets = vec![VecDeque::new(); *buckets],
+            SortEvent::Pass(pass) => self.pass = *pass,
+            SortEvent::Bucket { index, bucket } => {
+                self.buckets[*bucket].push_back(self.data[*index]);
+                self.highlights.push((*index, Highlight::Compared));
+                self.bucket = Some(*bucket);
+            },
+            SortEvent::Drain { bucket, index } => {
+                if let Some(value) = self.buckets[*bucket].pop_front() {
+                    self.data[*index] = value;
+                }
+                self.highlights.push((*index, Highlight::Changed));
+                self.bucket = Some(*bucket);
+            },
+            SortEvent::EnterRange(start, end) => {
+                self.stack.push((*start, *end));
+                self.pivot = None;
+            },
+            SortEvent::LeaveRange(start, end) => {
+                let range = self.stack.pop();
+                debug_assert_eq!(range, Some((*start, *end)), "left a range other than the innermost");
+                self.pivot = None;
+            },
+            SortEvent::Allocate(start, end) => {
+                self.buffers.push(Buffer { start: *start, values: self.data[*start..*end].to_vec(), cursor: 0 });
+                self.uses_buffers = true;
+            },
+            SortEvent::Free(start, _) => self.buffers.retain(|buffer| buffer.start != *start),
+            SortEvent::Restore { from, index } => {
+                if let Some(buffer) = self.buffers.iter_mut().find(|buffer| buffer.covers(*from)) {
+                    self.data[*index] = buffer.values[from - buffer.start];
+                    buffer.cursor = from - buffer.start + 1;
+                }
+                self.buffer_highlights.push((*from, Highlight::Changed));
+                self.highlights.push((*index, Highlight::Changed));
+            },
+            SortEvent::Shuffle(data) => self.data.clone_from(data),
+            SortEvent::Trace { .. } => (),
+            SortEvent::Finish => {
+                self.line = None;
+                self.vars.clear();
+                self.pivot = None;
+                self.gap = None;
+                self.chain = None;
+                self.runs.clear();
+                self.finished = true;
+            },
+        }
+
+        self.metrics.record(event);
+    }
+
+    /* Mark index as compared, moving the cursor of the buffer holding it if there is one */
+    fn read(&mut self, index: usize) {
+        match self.buffers.iter_mut().find(|buffer| buffer.covers(index)) {
+            Some(buffer) => {
+                buffer.cursor = index - buffer.start;
+                self.buffer_highlights.push((index, Highlight::Compared));
+            },
+            None => self.highlights.push((index, Highlight::Compared)),
+        }
+    }
+
     pub fn get_data(&self) -> &Vec<u64> {
         &self.data
     }
-    
+
+    /* Check if data is sorted */
     pub fn is_sorted(&self) -> bool {
-        self.is_sorted
-    } 
-    
+        self.data.windows(2).all(|w| w[0] <= w[1])
+    }
+
+    /* Check if sort has completed */
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /* Get role of index in the last operation, if any */
+    pub fn highlight(&self, index: usize) -> Option<Highlight> {
+        self.highlights.iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, highlight)| *highlight)
+            .or((self.pivot == Some(index)).then_some(Highlight::Pivot))
+    }
+
+    /* Live auxiliary buffers, in order of allocation */
+    pub fn get_buffers(&self) -> &Vec<Buffer> {
+        &self.buffers
+    }
+
+    /* Role of the buffered copy of data[index] in the last operation, if any */
+    pub fn buffer_highlight(&self, index: usize) -> Option<Highlight> {
+        self.buffer_highlights.iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, highlight)| *highlight)
+    }
+
+    /* Check if sort has copied data into buffers */
+    pub fn uses_buffers(&self) -> bool {
+        self.uses_buffers
+    }
+
+    /* Ranges of the active recursive calls, outermost first */
+    pub fn get_stack(&self) -> &Vec<(usize, usize)> {
+        &self.stack
+    }
+
+    /* Check if index is in the range of the innermost recursive call, or there is none */
+    pub fn in_active_range(&self, index: usize) -> bool {
+        self.stack.last().is_none_or(|(start, end)| (*start .. *end).contains(&index))
+    }
+
+    /* Pseudocode of the sort, as run with its options */
+    pub fn get_pseudocode(&self) -> &'static [&'static str] {
+        pseudocode::listing(self.sort_type, &self.options)
+    }
+
+    /* Line of pseudocode being run, numbered from 1 */
+    pub fn get_line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /* Name and value of each variable at the current line */
+    pub fn get_vars(&self) -> &Vec<(&'static str, usize)> {
+        &self.vars
+    }
+
+    /* Current gap of shell sort */
+    pub fn get_gap(&self) -> Option<usize> {
+        self.gap
+    }
+
+    /* Check if index is in the h-chain last compared by shell sort */
+    pub fn in_chain(&self, index: usize) -> bool {
+        self.gap.zip(self.chain).is_some_and(|(gap, chain)| index % gap == chain)
+    }
+
+    /* Sorted runs found or merged by timsort, in order */
+    pub fn get_runs(&self) -> &Vec<(usize, usize)> {
+        &self.runs
+    }
+
+    /* End of the heap in data, if sort uses one */
+    pub fn get_heap(&self) -> Option<usize> {
+        self.heap
+    }
+
+    /* Buckets used by distribution sorts */
+    pub fn get_buckets(&self) -> &Vec<VecDeque<u64>> {
+        &self.buckets
+    }
+
+    /* Bucket used by the last operation, if any */
+    pub fn get_bucket(&self) -> Option<usize> {
+        self.bucket
+    }
+
+    /* Current digit pass of distribution sorts */
+    pub fn get_pass(&self) -> usize {
+        self.pass
+    }
+
     pub fn get_sort_type(&self) -> SortType {
         self.sort_type
     }
-    
-    pub fn get_count(&self) -> &Count {
-        &self.count
-    } 
+
+    /* Whether the sort recurses, as run with its options */
+    pub fn is_divide_and_conquer(&self) -> bool {
+        self.sort_type.is_divide_and_conquer(&self.options)
+    }
+
+    /* Description of the options the sort was run with */
+    pub fn get_variant(&self) -> Option<String> {
+        self.options.variant(self.sort_type)
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_metrics(&self) -> &Metrics {
+        &self.metrics
+    }
 }
 
 
 pub struct Sort<'a, R: Renderer> {
     renderer: &'a mut R,
-	sort: SortType,
-    count: Count,
-    data: Vec<u64>,
+    state: SortSnapshot,
     tick_rate: u64,
+    rng: StdRng,
+    options: SortOptions,
 }
 
 
 impl<'a, R: Renderer> Sort<'a, R> {
-    pub fn new(renderer: &'a mut R, sort: SortType, quantity: usize, tick_rate: u64) -> Sort<R> {
+    pub fn new(renderer: &'a mut R, sort: SortType, data: Vec<u64>, tick_rate: u64, seed: u64, options: SortOptions) -> Sort<'a, R> {
         Sort {
             renderer,
-            sort,
-            data: gen_data(quantity),
+            state: SortSnapshot::new(sort, options, seed, data),
             tick_rate,
-            count: Count::new(sort.count_type()),
+            rng: StdRng::seed_from_u64(seed ^ SORT_SEED_MIX),
+            options,
         }
     }
 
-    pub fn from_args(renderer: &'a mut R, args: Args) -> Sort<R> {
-        Sort::new(
-            renderer,
-            args.sort_type, 
-            (args.quantity as u64).try_into().unwrap(), 
-            args.tick_rate
-        )
-    }
-    
-    /* Generate snapshot to render */
-    pub fn snapshot(&self) -> SortSnapshot {
-        SortSnapshot {
-            data: self.data.clone(),
-            is_sorted: self.is_sorted(),
-            sort_type: self.sort,
-            count: self.count.clone(),
+    /* Check if data is sorted, comparing each adjacent pair */
+    fn check_sorted(&mut self) -> Result<bool, Error> {
+        for i in 0 .. self.state.data.len() - 1 {
+            if self.compare(i, i + 1)?.is_gt() {
+                return Ok(false);
+            }
         }
+
+        Ok(true)
     }
 
-    /* Check if data is sorted */
-	pub fn is_sorted(&self) -> bool {
-        self.data.windows(2).all(|w| w[0] <= w[1])
-	}
+    /* Apply event to data and pass it to renderer, following its control decisions */
+    fn tick(&mut self, event: SortEvent, duration: Duration) -> Result<(), Error> {
+        self.state.apply(&event);
+        let mut control = self.renderer.tick(&event, duration)?;
+
+        loop {
+            match control {
+                Control::Continue => return Ok(()),
+                Control::Quit => return Err(Error::Interrupted),
+                Control::Faster => self.tick_rate = (self.tick_rate / 2).clamp(MIN_TICK, MAX_TICK),
+                Control::Slower => self.tick_rate = (self.tick_rate * 2).clamp(MIN_TICK, MAX_TICK),
+            }
+
+            control = self.renderer.sleep(Duration::from_millis(self.tick_rate))?;
+        }
+    }
+
+    /* Emit event, waiting for one tick */
+    fn emit(&mut self, event: SortEvent) -> Result<(), Error> {
+        self.tick(event, Duration::from_millis(self.tick_rate))
+    }
+
+    /* Compare data[i] and data[j] */
+    fn compare(&mut self, i: usize, j: usize) -> Result<Ordering, Error> {
+        self.emit(SortEvent::Compare(i, j))?;
+        Ok(self.state.data[i].cmp(&self.state.data[j]))
+    }
+
+    /* Swap data[i] and data[j] */
+    fn swap(&mut self, i: usize, j: usize) -> Result<(), Error> {
+        self.emit(SortEvent::Swap(i, j))
+    }
+
+    /* Write value to data[index] */
+    fn write(&mut self, index: usize, value: u64) -> Result<(), Error> {
+        self.emit(SortEvent::Write { index, value })
+    }
+
+    /* Move to line of the pseudocode, with current values of its variables, without waiting */
+    fn trace(&mut self, line: usize, vars: &[(&'static str, usize)]) -> Result<(), Error> {
+        let event = SortEvent::Trace { line, vars: vars.to_vec() };
+        self.state.apply(&event);
+        self.renderer.render(&event)
+    }
+
+    /* Write buffered copy of data[from] back to data[index] */
+    fn restore(&mut self, from: usize, index: usize) -> Result<(), Error> {
+        self.emit(SortEvent::Restore { from, index })
+    }
 
     /* Run the sorting algorithm, rendering to terminal */
-    pub fn run(mut self) -> Result<Count, Error> {
-        self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
-        
-        match self.sort {
+    pub fn run(mut self) -> Result<Metrics, Error> {
+        self.emit(SortEvent::Start {
+            sort_type: self.state.sort_type,
+            options: self.options,
+            seed: self.state.seed,
+            data: self.state.data.clone()
+        })?;
+
+        match self.state.sort_type {
             SortType::Bogo => self.bogosort(),
             SortType::Bubble => self.bubble_sort(),
             SortType::Insertion => self.insertion_sort(),
+            SortType::Shell => self.shell_sort(),
             SortType::Merge => self.merge_sort(),
+            SortType::Tim => self.tim_sort(),
             SortType::Quick => self.quick_sort(),
+            SortType::Heap => self.heap_sort(),
+            SortType::Counting => self.counting_sort(),
+            SortType::Radix => self.radix_sort(),
         }?;
 
-        self.renderer.tick(self.snapshot(), Duration::from_millis(5000))?;
+        self.emit(SortEvent::Finish)?;
 
-        Ok(self.count)
+        Ok(self.state.metrics)
 	}
 
     /* Perform bogosort */
     fn bogosort(&mut self) -> Result<(), Error> {
-        let mut rng = rand::thread_rng();
-
         loop {
-            if self.is_sorted() { break; }
-            
-            self.data.shuffle(&mut rng);
-            self.count.increment();
-            
-            self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
-        }
+            self.trace(1, &[])?;
+            if self.check_sorted()? {
+                return Ok(());
+            }
 
-        Ok(())
+            self.trace(2, &[])?;
+            let mut data = self.state.data.clone();
+            data.shuffle(&mut self.rng);
+
+            self.emit(SortEvent::Shuffle(data))?;
+        }
     }
 
     /* Perform bubble sort */
     fn bubble_sort(&mut self) -> Result<(), Error> {
         let mut swapped: bool;
+        let len = self.state.data.len();
 
-        for i in 0 .. self.data.len() - 1 {
+        for i in 0 .. len - 1 {
             swapped = false;
 
-            for j in 0 .. self.data.len() - i - 1 {
-                if self.data[j] > self.data[j + 1] {
-                    self.data.swap(j, j + 1);
-                    self.count.increment();
-
-                    self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
-
+            for j in 0 .. len - i - 1 {
+                self.trace(4, &[("i", i), ("j", j)])?;
+                if self.compare(j, j + 1)?.is_gt() {
+                    self.trace(5, &[("i", i), ("j", j)])?;
+                    self.swap(j, j + 1)?;
                     swapped = true;
                 }
             }
-    
+
             if !swapped {
                 break;
             }
@@ -134,40 +498,111 @@ impl<'a, R: Renderer> Sort<'a, R> {
 
     /* Perform insertion sort */
     fn insertion_sort(&mut self) -> Result<(), Error> {
-        for i in 1 .. self.data.len() {
-            let key = self.data[i];
+        for i in 1 .. self.state.data.len() {
+            /* Carry key in a buffer, leaving its slot free to shift into */
+            let key = self.state.data[i];
+            self.trace(2, &[("i", i), ("key", key as usize)])?;
+            self.emit(SortEvent::Allocate(i, i + 1))?;
             let mut j = i;
-    
+
             /* Move elements forward if greater than key */
-            while j > 0 && self.data[j - 1] > key {
-                self.data[j] = self.data[j - 1];
+            while j > 0 {
+                self.trace(4, &[("i", i), ("j", j), ("key", key as usize)])?;
+                self.emit(SortEvent::Compare(j - 1, i))?;
+                if self.state.data[j - 1] <= key {
+                    break;
+                }
+
+                self.trace(5, &[("i", i), ("j", j), ("key", key as usize)])?;
+                self.write(j, self.state.data[j - 1])?;
                 j -= 1;
-                
-                self.count.increment();
-                self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
             }
 
-            self.data[j] = key;
+            self.trace(7, &[("i", i), ("j", j), ("key", key as usize)])?;
+            self.restore(i, j)?;
+            self.emit(SortEvent::Free(i, i + 1))?;
+        }
+
+        Ok(())
+    }
+
+    /* Perform shell sort, insertion sorting every gap-th element for each gap in sequence */
+    fn shell_sort(&mut self) -> Result<(), Error> {
+        let len = self.state.data.len();
+
+        for gap in self.options.gaps.sequence(len) {
+            self.trace(1, &[("gap", gap)])?;
+            self.emit(SortEvent::Gap(gap))?;
+
+            for i in gap .. len {
+                let mut j = i;
+
+                while j >= gap {
+                    self.trace(4, &[("gap", gap), ("i", i), ("j", j)])?;
+                    if self.compare(j - gap, j)?.is_le() {
+                        break;
+                    }
+
+                    self.trace(5, &[("gap", gap), ("i", i), ("j", j)])?;
+                    self.swap(j - gap, j)?;
+                    j -= gap;
+                }
+            }
         }
-        
+
         Ok(())
     }
 
 
     /* Perform merge sort */
     fn merge_sort(&mut self) -> Result<(), Error> {
-        self.merge_sort_helper(0, self.data.len())
+        match self.options.merge {
+            Merge::TopDown | Merge::InPlace => self.merge_sort_helper(0, self.state.data.len()),
+            Merge::BottomUp => self.bottom_up_merge_sort(),
+        }
+    }
+
+    /* Merge sort iterative function, merging adjacent runs of doubling width */
+    fn bottom_up_merge_sort(&mut self) -> Result<(), Error> {
+        let len = self.state.data.len();
+        let mut width = 1;
+
+        while width < len {
+            for left in (0 .. len - width).step_by(2 * width) {
+                let mid = left + width;
+                let right = (mid + width).min(len);
+                let vars = [("width", width), ("left", left), ("mid", mid), ("right", right)];
+
+                self.trace(5, &vars)?;
+                self.merge(left, mid, right)?;
+            }
+
+            width *= 2;
+        }
+
+        Ok(())
     }
 
     /* Merge sort recursive indexed function */
     fn merge_sort_helper(&mut self, left: usize, right: usize) -> Result<(), Error> {
         if left < right - 1 {
+            self.trace(2, &[("left", left), ("right", right)])?;
+            self.emit(SortEvent::EnterRange(left, right))?;
+
             let mid: usize = left + (right - left) / 2;
 
+            self.trace(4, &[("left", left), ("mid", mid)])?;
             self.merge_sort_helper(left, mid)?;
+            self.trace(5, &[("mid", mid), ("right", right)])?;
             self.merge_sort_helper(mid, right)?;
 
-            self.merge(left, mid, right)?;
+            self.trace(6, &[("left", left), ("mid", mid), ("right", right)])?;
+            match self.options.merge {
+                Merge::InPlace => self.in_place_merge(left, mid, right)?,
+                _ => self.merge(left, mid, right)?,
+            }
+
+            self.emit(SortEvent::LeaveRange(left, right))?;
         }
 
         Ok(())
@@ -176,8 +611,12 @@ impl<'a, R: Renderer> Sort<'a, R> {
     /* Merge together data[left..mid) and data[mid..right) */
     fn merge(&mut self, left: usize, mid: usize, right: usize) -> Result<(), Error> {
         /* Temp arrays to save values */
-        let left_data: Vec<u64> = self.data[left..mid].to_vec().clone();
-        let right_data: Vec<u64> = self.data[mid..right].to_vec().clone();
+        self.trace(9, &[("left", left), ("mid", mid), ("right", right)])?;
+        self.emit(SortEvent::Allocate(left, mid))?;
+        self.emit(SortEvent::Allocate(mid, right))?;
+
+        let left_data: Vec<u64> = self.state.data[left..mid].to_vec();
+        let right_data: Vec<u64> = self.state.data[mid..right].to_vec();
 
         let mut i: usize = 0;
         let mut j: usize = 0;
@@ -186,86 +625,777 @@ impl<'a, R: Renderer> Sort<'a, R> {
 
         /* Merge temp arrays back into data */
         while i < left_data.len() && j < right_data.len() {
+            self.trace(12, &[("i", i), ("j", j), ("k", k)])?;
+            self.emit(SortEvent::Compare(left + i, mid + j))?;
+
             if left_data[i] <= right_data[j] {
-                self.data[k] = left_data[i];
+                self.trace(13, &[("i", i), ("j", j), ("k", k)])?;
+                self.restore(left + i, k)?;
                 i += 1;
             } else {
-                self.data[k] = right_data[j];
+                self.trace(15, &[("i", i), ("j", j), ("k", k)])?;
+                self.restore(mid + j, k)?;
                 j += 1;
             }
-            
+
             k += 1;
-            
-            self.count.increment();
-            self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
         }
 
-        /* Copy remaining left array elements into data */
-        for i in i..left_data.len() {
-            self.data[k] = left_data[i];
+        /* Copy remaining left and right array elements into data */
+        self.trace(17, &[("i", i), ("j", j), ("k", k)])?;
+        for from in (left + i .. mid).chain(mid + j .. right) {
+            self.restore(from, k)?;
             k += 1;
+        }
 
-            self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
+        self.emit(SortEvent::Free(left, mid))?;
+        self.emit(SortEvent::Free(mid, right))?;
+
+        Ok(())
+    }
+
+    /* Merge together data[left..mid) and data[mid..right) without copies, by rotating the middle of each half into place */
+    fn in_place_merge(&mut self, left: usize, mid: usize, right: usize) -> Result<(), Error> {
+        if left == mid || mid == right {
+            return Ok(());
         }
 
-        /* Copy remaining right array elements into data */
-        for j in j..right_data.len() {
-            self.data[k] = right_data[j];
-            k += 1;
+        self.emit(SortEvent::EnterRange(left, right))?;
+
+        if right - left == 2 {
+            self.trace(11, &[("left", left), ("mid", mid)])?;
+            if self.compare(mid, left)?.is_lt() {
+                self.swap(left, mid)?;
+            }
+        } else {
+            /* Split the longer half in the middle, and the other half where that value belongs */
+            self.trace(14, &[("left", left), ("mid", mid), ("right", right)])?;
+            let (left_cut, right_cut) = if mid - left >= right - mid {
+                let left_cut = left + (mid - left) / 2;
+                (left_cut, self.bound(mid, right, left_cut, false)?)
+            } else {
+                let right_cut = mid + (right - mid) / 2;
+                (self.bound(left, mid, right_cut, true)?, right_cut)
+            };
+
+            /* Swap data[left_cut..mid) with data[mid..right_cut) */
+            self.trace(15, &[("left_cut", left_cut), ("mid", mid), ("right_cut", right_cut)])?;
+            self.rotate(left_cut, mid, right_cut)?;
+            let new_mid = left_cut + (right_cut - mid);
+
+            self.trace(16, &[("left", left), ("left_cut", left_cut), ("new_mid", new_mid)])?;
+            self.in_place_merge(left, left_cut, new_mid)?;
+            self.trace(17, &[("new_mid", new_mid), ("right_cut", right_cut), ("right", right)])?;
+            self.in_place_merge(new_mid, right_cut, right)?;
+        }
+
+        self.emit(SortEvent::LeaveRange(left, right))
+    }
+
+    /* First index in sorted data[start..end) whose value is above data[key], or not below if include_equal is false */
+    fn bound(&mut self, mut start: usize, mut end: usize, key: usize, include_equal: bool) -> Result<usize, Error> {
+        while start < end {
+            let mid = start + (end - start) / 2;
+            let ordering = self.compare(mid, key)?;
+
+            if ordering.is_lt() || (include_equal && ordering.is_eq()) {
+                start = mid + 1;
+            } else {
+                end = mid;
+            }
+        }
+
+        Ok(start)
+    }
+
+    /* Move data[mid..end) before data[start..mid) by reversing both then the whole range */
+    fn rotate(&mut self, start: usize, mid: usize, end: usize) -> Result<(), Error> {
+        self.reverse(start, mid)?;
+        self.reverse(mid, end)?;
+        self.reverse(start, end)
+    }
+
+    /* Reverse data[start..end) */
+    fn reverse(&mut self, start: usize, end: usize) -> Result<(), Error> {
+        for i in 0 .. (end - start) / 2 {
+            self.swap(start + i, end - 1 - i)?;
+        }
+
+        Ok(())
+    }
+
+
+    /* Perform timsort, merging natural runs extended to a minimum length */
+    fn tim_sort(&mut self) -> Result<(), Error> {
+        let len = self.state.data.len();
+        let min_run = min_run(len);
+
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        let mut start = 0;
+
+        while start < len {
+            self.trace(3, &[("min_run", min_run), ("start", start)])?;
+            let mut end = self.find_run(start)?;
+
+            /* Extend short runs to min_run with binary insertion */
+            let forced_end = (start + min_run).min(len);
+            if end < forced_end {
+                self.trace(5, &[("min_run", min_run), ("start", start), ("end", end)])?;
+                self.binary_insertion_sort(start, end, forced_end)?;
+                end = forced_end;
+            }
+
+            self.trace(6, &[("min_run", min_run), ("start", start), ("end", end)])?;
+            self.emit(SortEvent::Run(start, end))?;
+            runs.push((start, end));
+            self.merge_collapse(&mut runs, false)?;
+
+            start = end;
+        }
+
+        self.merge_collapse(&mut runs, true)
+    }
+
+    /* Find end of the run starting at data[start], reversing it if strictly descending */
+    fn find_run(&mut self, start: usize) -> Result<usize, Error> {
+        let len = self.state.data.len();
+        let mut end = start + 1;
+
+        if end == len {
+            return Ok(end);
+        }
+
+        if self.compare(end, end - 1)?.is_lt() {
+            end += 1;
+            while end < len && self.compare(end, end - 1)?.is_lt() {
+                end += 1;
+            }
+
+            self.reverse(start, end)?;
+        } else {
+            end += 1;
+            while end < len && self.compare(end, end - 1)?.is_ge() {
+                end += 1;
+            }
+        }
+
+        Ok(end)
+    }
 
-            self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
+    /* Insert each of data[sorted..end) into sorted data[start..sorted), finding positions by binary search */
+    fn binary_insertion_sort(&mut self, start: usize, sorted: usize, end: usize) -> Result<(), Error> {
+        for i in sorted .. end {
+            let mut low = start;
+            let mut high = i;
+
+            while low < high {
+                let mid = low + (high - low) / 2;
+
+                if self.compare(i, mid)?.is_lt() {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+
+            /* Shift larger values right to make room */
+            let value = self.state.data[i];
+            for k in (low .. i).rev() {
+                self.write(k + 1, self.state.data[k])?;
+            }
+            self.write(low, value)?;
+        }
+
+        Ok(())
+    }
+
+    /* Merge runs on top of the stack until their lengths shrink like the Fibonacci numbers, or all runs if forced */
+    fn merge_collapse(&mut self, runs: &mut Vec<(usize, usize)>, force: bool) -> Result<(), Error> {
+        let run_len = |runs: &Vec<(usize, usize)>, i: usize| runs[i].1 - runs[i].0;
+
+        while runs.len() > 1 {
+            let mut n = runs.len() - 2;
+
+            let unbalanced = (n > 0 && run_len(runs, n - 1) <= run_len(runs, n) + run_len(runs, n + 1))
+                || (n > 1 && run_len(runs, n - 2) <= run_len(runs, n - 1) + run_len(runs, n));
+
+            if force || unbalanced {
+                if n > 0 && run_len(runs, n - 1) < run_len(runs, n + 1) {
+                    n -= 1;
+                }
+            } else if run_len(runs, n) > run_len(runs, n + 1) {
+                return Ok(());
+            }
+
+            let (left, mid) = runs[n];
+            let (_, right) = runs.remove(n + 1);
+            runs[n] = (left, right);
+
+            let line = if force { 9 } else { 8 };
+            self.trace(line, &[("left", left), ("mid", mid), ("right", right)])?;
+            self.gallop_merge(left, mid, right)?;
+
+            self.trace(line, &[("left", left), ("mid", mid), ("right", right)])?;
+            self.emit(SortEvent::Run(left, right))?;
         }
 
         Ok(())
     }
 
+    /* Merge runs data[left..mid) and data[mid..right), galloping through whichever keeps winning */
+    fn gallop_merge(&mut self, left: usize, mid: usize, right: usize) -> Result<(), Error> {
+        /* Values of left run already smaller than the right run are in place */
+        self.trace(12, &[("left", left), ("mid", mid), ("right", right)])?;
+        let left_run = self.state.data[left..mid].to_vec();
+        let left = left + self.gallop(&left_run, left, (mid, self.state.data[mid]), true)?;
+
+        /* Values of right run larger than the left run are in place */
+        let right_run = self.state.data[mid..right].to_vec();
+        let right = mid + self.gallop(&right_run, mid, (mid - 1, self.state.data[mid - 1]), false)?;
+
+        if left == mid || right == mid {
+            return Ok(());
+        }
+
+        /* Only the left run is copied, as writes never overtake the right run */
+        self.trace(13, &[("left", left), ("mid", mid), ("right", right)])?;
+        self.emit(SortEvent::Allocate(left, mid))?;
+        let left_data: Vec<u64> = self.state.data[left..mid].to_vec();
+
+        let mut i: usize = 0;
+        let mut j: usize = mid;
+        let mut k: usize = left;
+
+        let mut left_wins: usize = 0;
+        let mut right_wins: usize = 0;
+
+        while i < left_data.len() && j < right {
+            if left_wins >= MIN_GALLOP {
+                /* Copy every left value not larger than the next right value at once */
+                self.trace(16, &[("i", i), ("j", j), ("k", k)])?;
+                let count = self.gallop(&left_data[i..], left + i, (j, self.state.data[j]), true)?;
+                for from in left + i .. left + i + count {
+                    self.restore(from, k)?;
+                    k += 1;
+                }
+                i += count;
+                left_wins = 0;
+            } else if right_wins >= MIN_GALLOP {
+                /* Copy every right value smaller than the next left value at once */
+                self.trace(16, &[("i", i), ("j", j), ("k", k)])?;
+                let right_run = self.state.data[j..right].to_vec();
+                let count = self.gallop(&right_run, j, (left + i, left_data[i]), false)?;
+                for value in right_run[..count].iter() {
+                    self.write(k, *value)?;
+                    k += 1;
+                }
+                j += count;
+                right_wins = 0;
+            } else {
+                self.trace(17, &[("i", i), ("j", j), ("k", k)])?;
+                self.emit(SortEvent::Compare(left + i, j))?;
+
+                if self.state.data[j] < left_data[i] {
+                    self.trace(18, &[("i", i), ("j", j), ("k", k)])?;
+                    self.write(k, self.state.data[j])?;
+                    j += 1;
+                    right_wins += 1;
+                    left_wins = 0;
+                } else {
+                    self.trace(20, &[("i", i), ("j", j), ("k", k)])?;
+                    self.restore(left + i, k)?;
+                    i += 1;
+                    left_wins += 1;
+                    right_wins = 0;
+                }
+
+                k += 1;
+            }
+        }
+
+        /* Copy remaining left values, remaining right values are already in place */
+        self.trace(21, &[("i", i), ("j", j), ("k", k)])?;
+        for from in left + i .. mid {
+            self.restore(from, k)?;
+            k += 1;
+        }
+
+        self.emit(SortEvent::Free(left, mid))?;
+
+        Ok(())
+    }
+
+    /* Count values at the start of sorted run that go before key from index, probing exponentially then by binary search */
+    fn gallop(&mut self, run: &[u64], run_start: usize, (index, key): (usize, u64), include_equal: bool) -> Result<usize, Error> {
+        let goes_before = |sort: &mut Self, i: usize| -> Result<bool, Error> {
+            sort.emit(SortEvent::Compare(run_start + i, index))?;
+            Ok(if include_equal { run[i] <= key } else { run[i] < key })
+        };
+
+        let mut low = 0;
+        let mut offset = 1;
+
+        while offset <= run.len() && goes_before(self, offset - 1)? {
+            low = offset;
+            offset = 2 * offset + 1;
+        }
+
+        let mut high = (offset - 1).min(run.len());
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            if goes_before(self, mid)? {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(low)
+    }
+
 
     /* Perform quick sort */
     fn quick_sort(&mut self) -> Result<(), Error> {
-        self.quick_sort_helper(0, self.data.len())
+        self.quick_sort_helper(0, self.state.data.len())
     }
 
     /* Quick sort recursive function */
     fn quick_sort_helper(&mut self, start: usize, end: usize) -> Result<(), Error> {
         if start < end {
-            let partition_index = self.partition(start, end)?;
-    
-            self.quick_sort_helper(start, partition_index)?;
-            self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
-            
-            self.quick_sort_helper(partition_index + 1, end)?;
-            self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
+            self.trace(2, &[("start", start), ("end", end)])?;
+            self.emit(SortEvent::EnterRange(start, end))?;
+
+            self.trace(3, &[("start", start), ("end", end)])?;
+            let pivot = self.choose_pivot(start, end)?;
+            self.emit(SortEvent::Pivot(pivot))?;
+
+            let (left_end, right_start) = match self.options.partition {
+                Partition::Lomuto => self.lomuto_partition(start, end, pivot)?,
+                Partition::Hoare => self.hoare_partition(start, end, pivot)?,
+                Partition::ThreeWay => self.three_way_partition(start, end, pivot)?,
+            };
+
+            self.trace(5, &[("start", start), ("left", left_end)])?;
+            self.quick_sort_helper(start, left_end)?;
+            self.trace(6, &[("right", right_start), ("end", end)])?;
+            self.quick_sort_helper(right_start, end)?;
+
+            self.emit(SortEvent::LeaveRange(start, end))?;
         }
 
         Ok(())
     }
 
-    /* Partition slice start to end, using data[end - 1] as pivot */
-    fn partition(&mut self, start: usize, end: usize) -> Result<usize, Error> {
-        let pivot = self.data[end - 1];
+    /* Choose index of pivot in data[start..end) */
+    fn choose_pivot(&mut self, start: usize, end: usize) -> Result<usize, Error> {
+        let last = end - 1;
+        let middle = start + (end - start) / 2;
+
+        match self.options.pivot {
+            Pivot::First => Ok(start),
+            Pivot::Last => Ok(last),
+            Pivot::Middle => Ok(middle),
+            Pivot::Random => Ok(self.rng.gen_range(start..end)),
+            Pivot::Ninther if end - start >= NINTHER_MIN => {
+                /* Median of the medians of three evenly spread triples */
+                let step = (end - start) / 8;
+                let low = self.median_of_three(start, start + step, start + 2 * step)?;
+                let mid = self.median_of_three(middle - step, middle, middle + step)?;
+                let high = self.median_of_three(last - 2 * step, last - step, last)?;
+
+                self.median_of_three(low, mid, high)
+            },
+            Pivot::MedianOfThree | Pivot::Ninther => self.median_of_three(start, middle, last),
+        }
+    }
+
+    /* Index of the median of data[a], data[b] and data[c] */
+    fn median_of_three(&mut self, a: usize, b: usize, c: usize) -> Result<usize, Error> {
+        let a_less_b = self.compare(a, b)?.is_lt();
+        let b_less_c = self.compare(b, c)?.is_lt();
+
+        if a_less_b == b_less_c {
+            return Ok(b);
+        }
+
+        let a_less_c = self.compare(a, c)?.is_lt();
+        Ok(if a_less_b == a_less_c { c } else { a })
+    }
+
+    /* Partition data[start..end) by sweeping values not above pivot to the front, returning ranges left to sort */
+    fn lomuto_partition(&mut self, start: usize, end: usize, pivot: usize) -> Result<(usize, usize), Error> {
+        let last = end - 1;
+        if pivot != last {
+            self.trace(9, &[("pivot", self.state.data[pivot] as usize)])?;
+            self.swap(pivot, last)?;
+        }
+
+        let mut i = start;
+
+        for j in start .. last {
+            let vars = [("i", i), ("j", j), ("pivot", self.state.data[last] as usize)];
+
+            self.trace(12, &vars)?;
+            if self.compare(j, last)?.is_le() {
+                self.trace(13, &vars)?;
+                self.swap(i, j)?;
+                i += 1;
+            }
+        }
+
+        self.trace(15, &[("i", i), ("pivot", self.state.data[last] as usize)])?;
+        self.swap(i, last)?;
+
+        Ok((i, i + 1))
+    }
+
+    /* Partition data[start..end) by swapping out of place pairs found from both ends, returning ranges left to sort */
+    fn hoare_partition(&mut self, start: usize, end: usize, pivot: usize) -> Result<(usize, usize), Error> {
+        if pivot != start {
+            self.trace(9, &[("pivot", self.state.data[pivot] as usize)])?;
+            self.swap(pivot, start)?;
+        }
+
+        let pivot_value = self.state.data[start] as usize;
         let mut i = start;
-        
-        for j in start .. end - 1 {
-            if self.data[j] <= pivot {
-                self.count.increment();
-                self.data.swap(i, j);
-                self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
-                
+        let mut j = end;
+
+        loop {
+            i += 1;
+            while i < end {
+                self.trace(12, &[("i", i), ("j", j), ("pivot", pivot_value)])?;
+                if self.compare(i, start)?.is_ge() {
+                    break;
+                }
                 i += 1;
             }
+
+            j -= 1;
+            loop {
+                self.trace(13, &[("i", i), ("j", j), ("pivot", pivot_value)])?;
+                if self.compare(j, start)?.is_le() {
+                    break;
+                }
+                j -= 1;
+            }
+
+            if i >= j {
+                break;
+            }
+
+            self.trace(15, &[("i", i), ("j", j), ("pivot", pivot_value)])?;
+            self.swap(i, j)?;
         }
-        
-        self.data.swap(i, end - 1);
-        self.renderer.tick(self.snapshot(), Duration::from_millis(self.tick_rate))?;
 
-        Ok(i)
+        self.trace(16, &[("j", j), ("pivot", pivot_value)])?;
+        self.swap(start, j)?;
+
+        Ok((j, j + 1))
+    }
+
+    /* Partition data[start..end) into values below, equal to and above pivot, returning ranges left to sort */
+    fn three_way_partition(&mut self, start: usize, end: usize, pivot: usize) -> Result<(usize, usize), Error> {
+        if pivot != start {
+            self.trace(9, &[("pivot", self.state.data[pivot] as usize)])?;
+            self.swap(pivot, start)?;
+        }
+
+        /* data[lt..i) are equal to pivot, data[gt..end) are above it */
+        let pivot_value = self.state.data[start] as usize;
+        let mut lt = start;
+        let mut i = start + 1;
+        let mut gt = end;
+
+        while i < gt {
+            self.trace(12, &[("lt", lt), ("i", i), ("gt", gt), ("pivot", pivot_value)])?;
+
+            match self.compare(i, lt)? {
+                Ordering::Less => {
+                    self.trace(13, &[("lt", lt), ("i", i), ("gt", gt), ("pivot", pivot_value)])?;
+                    self.swap(lt, i)?;
+                    lt += 1;
+                    i += 1;
+                },
+                Ordering::Greater => {
+                    gt -= 1;
+                    self.trace(16, &[("lt", lt), ("i", i), ("gt", gt), ("pivot", pivot_value)])?;
+                    self.swap(i, gt)?;
+                },
+                Ordering::Equal => i += 1,
+            }
+        }
+
+        Ok((lt, gt))
+    }
+
+
+    /* Perform heap sort */
+    fn heap_sort(&mut self) -> Result<(), Error> {
+        let len = self.state.data.len();
+
+        /* Build max heap from bottom up */
+        self.trace(1, &[])?;
+        self.emit(SortEvent::Heap(len))?;
+        for root in (0 .. len / 2).rev() {
+            self.sift_down(root, len)?;
+        }
+
+        /* Move largest to end and restore heap on remainder */
+        for end in (1 .. len).rev() {
+            self.trace(4, &[("end", end)])?;
+            self.swap(0, end)?;
+            self.emit(SortEvent::Heap(end))?;
+            self.sift_down(0, end)?;
+        }
+
+        Ok(())
+    }
+
+    /* Move data[root] down until larger than both children in data[..end) */
+    fn sift_down(&mut self, mut root: usize, end: usize) -> Result<(), Error> {
+        loop {
+            let left = 2 * root + 1;
+            let right = left + 1;
+            let mut largest = root;
+
+            self.trace(9, &[("root", root), ("end", end)])?;
+            if left < end && self.compare(left, largest)?.is_gt() {
+                largest = left;
+            }
+
+            if right < end && self.compare(right, largest)?.is_gt() {
+                largest = right;
+            }
+
+            if largest == root {
+                return Ok(());
+            }
+
+            self.trace(11, &[("root", root), ("largest", largest), ("end", end)])?;
+            self.swap(root, largest)?;
+            root = largest;
+        }
+    }
+
+
+    /* Perform counting sort, with one bucket per value in range */
+    fn counting_sort(&mut self) -> Result<(), Error> {
+        let (min, max) = self.scan(1)?;
+
+        /* Checked before adding one, as the full range of u64 has one more value than fits */
+        if max - min >= MAX_COUNTING_RANGE {
+            return Err(Error::RangeTooLarge((max - min).saturating_add(1)));
+        }
+        let range = max - min + 1;
+
+        self.trace(2, &[("min", min as usize), ("max", max as usize)])?;
+        self.emit(SortEvent::Buckets { buckets: range as usize, capacity: self.state.data.len() })?;
+        self.emit(SortEvent::Pass(1))?;
+
+        self.distribute((4, 8), |value| (value - min) as usize)
+    }
+
+    /* Perform least significant digit radix sort */
+    fn radix_sort(&mut self) -> Result<(), Error> {
+        let base = self.options.radix_base;
+        let (_, max) = self.scan(1)?;
+
+        self.trace(2, &[("base", base as usize), ("max", max as usize)])?;
+        self.emit(SortEvent::Buckets { buckets: base as usize, capacity: self.state.data.len() })?;
+
+        let mut place: u64 = 1;
+        let mut pass = 1;
+
+        loop {
+            self.trace(3, &[("place", place as usize)])?;
+            self.emit(SortEvent::Pass(pass))?;
+            self.distribute((5, 9), |value| (value / place % base) as usize)?;
+
+            /* Stop once every digit of the largest value has been sorted */
+            match place.checked_mul(base) {
+                Some(next) if next <= max => place = next,
+                _ => return Ok(()),
+            }
+
+            pass += 1;
+        }
+    }
+
+    /* Read every value, returning the smallest and largest */
+    fn scan(&mut self, line: usize) -> Result<(u64, u64), Error> {
+        for index in 0 .. self.state.data.len() {
+            self.trace(line, &[("i", index)])?;
+            self.emit(SortEvent::Read(index))?;
+        }
+
+        let min = self.state.data.iter().copied().min().unwrap_or(0);
+        let max = self.state.data.iter().copied().max().unwrap_or(0);
+
+        Ok((min, max))
+    }
+
+    /* Fill buckets with data in order, then drain them in order back into data, tracing the append and drain lines */
+    fn distribute(&mut self, (append_line, drain_line): (usize, usize), bucket_of: impl Fn(u64) -> usize) -> Result<(), Error> {
+        for index in 0 .. self.state.data.len() {
+            let bucket = bucket_of(self.state.data[index]);
+            self.trace(append_line, &[("i", index), ("bucket", bucket)])?;
+            self.emit(SortEvent::Bucket { index, bucket })?;
+        }
+
+        let mut index = 0;
+
+        for bucket in 0 .. self.state.buckets.len() {
+            while !self.state.buckets[bucket].is_empty() {
+                self.trace(drain_line, &[("bucket", bucket), ("k", index)])?;
+                self.emit(SortEvent::Drain { bucket, index })?;
+                index += 1;
+            }
+        }
+
+        Ok(())
     }
 
 }
 
+/* Length runs are extended to, so the number of runs is a power of two or just below */
+fn min_run(mut len: usize) -> usize {
+    let mut remainder = 0;
+
+    while len >= MIN_MERGE {
+        remainder |= len & 1;
+        len >>= 1;
+    }
+
+    len + remainder
+}
+
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::{bench::Headless, distribution::Distribution, gaps::Gaps, MAX_RADIX_BASE};
+
+    const OPTIONS: SortOptions = SortOptions {
+        radix_base: 10,
+        gaps: Gaps::Ciura,
+        merge: Merge::TopDown,
+        pivot: Pivot::Last,
+        partition: Partition::Lomuto,
+    };
+
+    /* Renderer applying events to its own snapshot, without drawing or waiting */
+    struct Replay {
+        snapshot: Option<SortSnapshot>,
+    }
+
+    impl Renderer for Replay {
+        fn render(&mut self, event: &SortEvent) -> Result<(), Error> {
+            match &mut self.snapshot {
+                Some(snapshot) => snapshot.apply(event),
+                None => self.snapshot = SortSnapshot::from_event(event),
+            }
+
+            Ok(())
+        }
+
+        fn sleep(&mut self, _duration: Duration) -> Result<Control, Error> {
+            Ok(Control::Continue)
+        }
+    }
+
+    /* Sort every distribution of a few quantities, checking the replayed data ends up in order */
+    fn assert_sorts(sort_type: SortType, options: SortOptions) {
+        /* Bogosort is only expected to finish on a handful of values */
+        let quantities: &[usize] = match sort_type {
+            SortType::Bogo => &[2, 5],
+            _ => &[2, 5, 40],
+        };
+
+        for distribution in Distribution::value_variants() {
+            for &quantity in quantities {
+                let data = distribution.generate(quantity, &mut StdRng::seed_from_u64(quantity as u64));
+                let mut expected = data.clone();
+                expected.sort();
+
+                let mut replay = Replay { snapshot: None };
+                let context = format!("{:?} of {} {:?} values with {:?}", sort_type, quantity, distribution, options);
 
-fn gen_data(quantity: usize) -> Vec<u64> {
-    let mut data: Vec<u64> = (1..(quantity as u64 + 1)).collect();
-    data.shuffle(&mut thread_rng());
-    return data;
+                Sort::new(&mut replay, sort_type, data, 0, 0, options).run().unwrap();
+
+                let snapshot = replay.snapshot.unwrap();
+                assert!(snapshot.is_finished(), "{}", context);
+                assert_eq!(snapshot.get_data(), &expected, "{}", context);
+            }
+        }
+    }
+
+    #[test]
+    fn sorts_every_combination() {
+        for sort_type in SortType::iter() {
+            for &pivot in Pivot::value_variants() {
+                for &partition in Partition::value_variants() {
+                    for &merge in Merge::value_variants() {
+                        for &gaps in Gaps::value_variants() {
+                            for radix_base in [2, 10, MAX_RADIX_BASE] {
+                                assert_sorts(sort_type, SortOptions { radix_base, gaps, merge, pivot, partition });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn counting_sort_rejects_full_range() {
+        let result = Sort::new(&mut Headless::new(SortType::Counting, &OPTIONS), SortType::Counting, vec![0, u64::MAX], 1, 0, OPTIONS).run();
+        assert!(matches!(result, Err(Error::RangeTooLarge(u64::MAX))));
+    }
+
+    #[test]
+    fn counting_sort_rejects_range_one_too_large() {
+        let result = Sort::new(&mut Headless::new(SortType::Counting, &OPTIONS), SortType::Counting, vec![5, 5 + MAX_COUNTING_RANGE], 1, 0, OPTIONS).run();
+        assert!(matches!(result, Err(Error::RangeTooLarge(range)) if range == MAX_COUNTING_RANGE + 1));
+
+        let result = Sort::new(&mut Headless::new(SortType::Counting, &OPTIONS), SortType::Counting, vec![5 + MAX_COUNTING_RANGE - 1, 5], 1, 0, OPTIONS).run();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn min_run_is_len_when_short() {
+        for len in 0 .. MIN_MERGE {
+            assert_eq!(min_run(len), len);
+        }
+    }
+
+    #[test]
+    fn min_run_is_between_half_and_min_merge() {
+        for len in MIN_MERGE ..= 5000 {
+            let run = min_run(len);
+            assert!((MIN_MERGE / 2 ..= MIN_MERGE).contains(&run), "min_run({}) = {}", len, run);
+        }
+    }
+
+    #[test]
+    fn min_run_splits_powers_of_two_evenly() {
+        for len in [16, 32, 64, 1024, 4096] {
+            let run = min_run(len);
+            assert_eq!(len % run, 0);
+            assert!((len / run).is_power_of_two());
+        }
+    }
+
+    #[test]
+    fn min_run_rounds_up_for_remainder() {
+        assert_eq!(min_run(17), 9);
+        assert_eq!(min_run(33), 9);
+        assert_eq!(min_run(100), 13);
+        assert_eq!(min_run(1000), 16);
+        assert_eq!(min_run(5000), 10);
+    }
 }
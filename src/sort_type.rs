@@ -4,15 +4,20 @@ use clap::ValueEnum;
 use ratatui::style::Color;
 use strum_macros::EnumIter;
 
-use crate::{analytics::{Analytics, Complexity, Notation, Rate}, count::CountType};
+use crate::{analytics::{Analytics, Complexity, Notation, Rate}, merge::Merge, SortOptions};
 
 #[derive(ValueEnum, EnumIter, Debug, Clone, Copy)]
 pub enum SortType {
 	Bogo,
 	Bubble,
 	Insertion,
+	Shell,
 	Merge,
-	Quick
+	Tim,
+	Quick,
+	Heap,
+	Counting,
+	Radix,
 }
 
 impl SortType {	
@@ -21,26 +26,35 @@ impl SortType {
 			SortType::Bogo => (219, 77, 59),
 			SortType::Bubble => (59, 126, 219),
 			SortType::Insertion => (219, 124, 59),
+			SortType::Shell => (63, 81, 181),
 			SortType::Merge => (42, 183, 44),
+			SortType::Tim => (233, 30, 99),
 			SortType::Quick => (240, 128, 128),
+			SortType::Heap => (0, 150, 136),
+			SortType::Counting => (156, 39, 176),
+			SortType::Radix => (255, 179, 0),
 		}		
 	}
 
-	pub fn color(&self) -> Color {
-		let (r, g, b) = self.rgb();
-		Color::Rgb(r, g, b)
+	/* Whether sort orders data by comparing values */
+	pub fn is_comparison(&self) -> bool {
+		!matches!(self, SortType::Counting | SortType::Radix)
 	}
 
-	pub fn count_type(&self) -> CountType {
+	/* Whether sort recurses into ranges of data when run with options, which bottom-up merge sort doesn't */
+	pub fn is_divide_and_conquer(&self, options: &SortOptions) -> bool {
 		match self {
-			SortType::Bogo => CountType::Shuffles,
-			SortType::Bubble => CountType::Comparisons,
-			SortType::Insertion => CountType::Comparisons,
-			SortType::Merge => CountType::Comparisons,
-			SortType::Quick => CountType::Comparisons,
+			SortType::Merge => !matches!(options.merge, Merge::BottomUp),
+			SortType::Quick => true,
+			_ => false,
 		}
 	}
 
+	pub fn color(&self) -> Color {
+		let (r, g, b) = self.rgb();
+		Color::Rgb(r, g, b)
+	}
+
 	/* Worst, average and best time complexities */
 	fn time_complexity(&self) -> (Complexity, Complexity, Complexity) {
 		match self {
@@ -54,16 +68,36 @@ impl SortType {
 				Complexity::big_o(Rate::Quadratic), 
 				Complexity::big_o(Rate::Linear), 
 			),
-			SortType::Merge => (
+			SortType::Shell => (
+				Complexity::big_o(Rate::Quadratic),
+				Complexity::big_o(Rate::NLogSquaredN),
+				Complexity::new(Notation::UpperOmega, Rate::NLogN),
+			),
+			SortType::Merge | SortType::Heap => (
 				Complexity::big_o(Rate::NLogN), 
 				Complexity::new(Notation::Theta, Rate::NLogN), 
 				Complexity::new(Notation::UpperOmega, Rate::NLogN), 
 			),
+			SortType::Tim => (
+				Complexity::big_o(Rate::NLogN),
+				Complexity::new(Notation::Theta, Rate::NLogN),
+				Complexity::new(Notation::UpperOmega, Rate::Linear),
+			),
 			SortType::Quick => (
 				Complexity::big_o(Rate::Quadratic),
 				Complexity::big_o(Rate::NLogN),
 				Complexity::big_o(Rate::NLogN),
 			),
+			SortType::Counting => (
+				Complexity::big_o(Rate::NPlusK),
+				Complexity::new(Notation::Theta, Rate::NPlusK),
+				Complexity::new(Notation::UpperOmega, Rate::NPlusK),
+			),
+			SortType::Radix => (
+				Complexity::big_o(Rate::NTimesK),
+				Complexity::new(Notation::Theta, Rate::NTimesK),
+				Complexity::new(Notation::UpperOmega, Rate::NTimesK),
+			),
 		}
 	}
 
@@ -73,8 +107,10 @@ impl SortType {
 			SortType::Bogo => Rate::Linear,
 			SortType::Bubble => Rate::Linear,
 			SortType::Insertion => Rate::Linear,
-			SortType::Merge => Rate::Linear,
+			SortType::Merge | SortType::Tim => Rate::Linear,
 			SortType::Quick => Rate::Linear,
+			SortType::Shell | SortType::Heap => Rate::Constant,
+			SortType::Counting | SortType::Radix => Rate::NPlusK,
 		})
 	}
 
@@ -90,8 +126,13 @@ impl Display for SortType {
 			SortType::Bogo => "Bogo",
 			SortType::Bubble => "Bubble",
 			SortType::Insertion => "Insertion",
+			SortType::Shell => "Shell",
 			SortType::Merge => "Merge",
+			SortType::Tim => "Tim",
 			SortType::Quick => "Quick",
+			SortType::Heap => "Heap",
+			SortType::Counting => "Counting",
+			SortType::Radix => "Radix",
 		})
 	}
 }
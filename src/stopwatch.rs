@@ -0,0 +1,32 @@
+use std::time::{Duration, Instant};
+
+/* Time spent sorting, paused while the renderer draws or waits */
+pub struct Stopwatch {
+	elapsed: Duration,
+	resumed: Option<Instant>,
+}
+
+impl Stopwatch {
+	pub fn new() -> Stopwatch {
+		Stopwatch {
+			elapsed: Duration::ZERO,
+			resumed: None,
+		}
+	}
+
+	/* Count time again once control returns to the sort */
+	pub fn resume(&mut self) {
+		self.resumed = Some(Instant::now());
+	}
+
+	/* Stop counting while control is away from the sort */
+	pub fn pause(&mut self) {
+		if let Some(resumed) = self.resumed.take() {
+			self.elapsed += resumed.elapsed();
+		}
+	}
+
+	pub fn get_elapsed(&self) -> Duration {
+		self.elapsed
+	}
+}
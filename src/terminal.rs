@@ -1,21 +1,65 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ratatui::{
 	DefaultTerminal, 
 	Frame,
-	style::{Style, Stylize}, 
-	text::{Line, Text, ToText}, 
-	layout::{Constraint, Layout}, 
-	crossterm::event::{self, Event, KeyCode, KeyEventKind}, 
-	widgets::{Bar, BarChart, BarGroup, Block, Borders, Clear, Padding, Paragraph}, 
+	style::{Color, Style, Stylize}, 
+	text::{Line, Span, Text, ToText}, 
+	layout::{Constraint, Layout, Rect}, 
+	crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers}, 
+	symbols::Marker,
+	widgets::{canvas::{Canvas, Circle, Points}, Bar, BarChart, BarGroup, Block, Borders, Clear, Gauge, Padding, Paragraph, Row, Table}, 
 };
 
-use crate::{sort::SortSnapshot, analytics::Analytics, Error, Renderer};
+use crate::{event::SortEvent, history::History, menu::Menu, stopwatch::Stopwatch, race::Standing, sort::{Buffer, Highlight, SortSnapshot}, analytics::Analytics, view::View, Control, Error, Renderer};
 
 const BAR_GAP_MAX: u16 = 1;
 const BAR_WIDTH_MIN: u16 = 1;
 const BAR_WIDTH_MAX: u16 = 3;
 
+const COMPARED_COLOR: Color = Color::Yellow;
+const CHANGED_COLOR: Color = Color::Red;
+const PIVOT_COLOR: Color = Color::Magenta;
+const SORTED_COLOR: Color = Color::White;
+const CHAIN_COLOR: Color = Color::LightCyan;
+const READ_COLOR: Color = Color::DarkGray;
+const OUTSIDE_COLOR: Color = Color::DarkGray;
+/* Alternating colours of run brackets, so neighbouring runs are distinct */
+const RUN_COLORS: [Color; 2] = [Color::LightGreen, Color::LightBlue];
+/* Colours taking over a cell of a dense chart, most important first */
+const HIGHLIGHT_COLORS: [Color; 4] = [CHANGED_COLOR, COMPARED_COLOR, PIVOT_COLOR, CHAIN_COLOR];
+
+/* Dots in each braille cell, and their bits from the top of each column */
+const DENSE_DOTS_ACROSS: usize = 2;
+const DENSE_DOTS_DOWN: usize = 4;
+const BRAILLE_DOTS: [[u32; DENSE_DOTS_DOWN]; DENSE_DOTS_ACROSS] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/* Degrees of hue spanned by values, from red to violet */
+const HUE_RANGE: f64 = 300.0;
+
+/* Total time taken by the finished sweep, and most frames drawn during it */
+const SWEEP_DURATION: Duration = Duration::from_millis(1000);
+const SWEEP_FRAMES: usize = 150;
+
+/* Percentage of terminal width taken by the side panel */
+const PANEL_PERCENT: u16 = 40;
+/* Columns around pseudocode taken by borders, padding, line numbers and spacing */
+const CODE_PAD: u16 = 9;
+
+/* Percentage of chart height taken by auxiliary buffers */
+const BUFFER_PERCENT: u16 = 35;
+/* Labels of the read cursors of each buffer, in order of allocation */
+const CURSOR_NAMES: [&str; 2] = ["i", "j"];
+
+/* Width of the launch menu, and of its algorithm column */
+const MENU_WIDTH: u16 = 80;
+const MENU_NAME_WIDTH: u16 = 16;
+const MENU_HELP: &str = "↑/↓ algorithm   Tab setting   ←/→ change   Enter run   q quit";
+
+/* How often input is polled while paused */
+const PAUSE_POLL: Duration = Duration::from_millis(250);
+
 #[derive(Debug, Clone, Copy)]
 struct BarSettings {
 	width: u16,
@@ -26,11 +70,11 @@ impl BarSettings {
 	/* Calculate width and gap of bars */
 	fn calc(term_width: u16, quantity: usize) -> Result<BarSettings, Error> {
 		let mut bar_settings = BarSettings::max();
-		let usable_term_width = term_width - HORIZ_PAD - CHART_PAD;
+		let usable_term_width = term_width.checked_sub(HORIZ_PAD + CHART_PAD).ok_or(Error::BarOverflow(quantity))?;
 
 		loop {
 			/* Width of all gaps */
-			let total_gap_width: u16 = bar_settings.gap * (quantity - 1) as u16;
+			let total_gap_width: u16 = bar_settings.gap * quantity.saturating_sub(1) as u16;
 			
 			/* If terminal width - total gaps > 0 */
 			if let Some(all_bars_width) = usable_term_width.checked_sub(total_gap_width) {
@@ -72,97 +116,1076 @@ impl BarSettings {
 const HORIZ_PAD: u16 = 4;
 const CHART_PAD: u16 = 2;
 
+/* Rows left above and below the chart, and each chart of a race */
+const CHART_MARGIN: u16 = 5;
+const RACE_MARGIN: u16 = 1;
+/* Fewest rows a chart can be drawn in, including its borders */
+const MIN_CHART_HEIGHT: u16 = 8;
+
 
 pub struct Terminal {
-	term: DefaultTerminal
+	term: DefaultTerminal,
+	snapshot: Option<SortSnapshot>,
+	history: History,
+	timeline: Option<(usize, usize)>,
+	show_panel: bool,
+	show_code: bool,
+	view: View,
+	paused: bool,
+	/* Quit during the finish sweep, so there is no timeline to scrub */
+	quit: bool,
+	interval: Duration,
+	stopwatch: Stopwatch,
+	race: Vec<(SortSnapshot, Standing)>,
 }
 
 impl Terminal {
     /* Initialise terminal to use for rendering chart */
-	pub fn new() -> Result<Terminal, Error> {
+	pub fn new(view: View) -> Result<Terminal, Error> {
 		Ok(Terminal {
-			term: ratatui::init()
+			/* Also installs a panic hook restoring the terminal before the panic is reported */
+			term: ratatui::try_init()?,
+			snapshot: None,
+			history: History::new(),
+			timeline: None,
+			show_panel: true,
+			show_code: false,
+			view,
+			paused: false,
+			quit: false,
+			interval: Duration::ZERO,
+			stopwatch: Stopwatch::new(),
+			race: Vec::new(),
 		})
 	}
 
+	/* Show snapshot and standing of each racing sort in a grid */
+	pub fn show_race(&mut self, lanes: Vec<(SortSnapshot, Standing)>) -> Result<(), Error> {
+		self.race = lanes;
+		self.redraw()
+	}
+
+	/* Wait until quit */
+	pub fn wait(&mut self) -> Result<(), Error> {
+		loop {
+			match event::read()? {
+				Event::Key(key) if key.kind == KeyEventKind::Press && is_quit(&key) => return Ok(()),
+				Event::Resize(..) => self.redraw()?,
+				_ => (),
+			}
+		}
+	}
+
+	/* Show menu until an algorithm is chosen to run, returning false if quit */
+	pub fn menu(&mut self, menu: &mut Menu) -> Result<bool, Error> {
+		loop {
+			self.term.draw(|frame| render_menu(frame, menu))?;
+
+			if let Event::Key(key) = event::read()? {
+				if key.kind != KeyEventKind::Press {
+					continue;
+				}
+
+				if is_quit(&key) {
+					return Ok(false);
+				}
+
+				match key.code {
+					KeyCode::Enter => return Ok(true),
+					KeyCode::Up => menu.select(false),
+					KeyCode::Down => menu.select(true),
+					KeyCode::Tab => menu.focus(true),
+					KeyCode::BackTab => menu.focus(false),
+					KeyCode::Left => menu.adjust(false),
+					KeyCode::Right => menu.adjust(true),
+					_ => (),
+				}
+			}
+		}
+	}
+
+	/* Forget rendered sort, ready to render another */
+	pub fn reset(&mut self) {
+		self.snapshot = None;
+		self.history = History::new();
+		self.timeline = None;
+		self.paused = false;
+		self.quit = false;
+		self.interval = Duration::ZERO;
+		self.stopwatch = Stopwatch::new();
+	}
+
+	/* Time spent sorting, excluding drawing and waiting between steps */
+	pub fn get_elapsed(&self) -> Duration {
+		self.stopwatch.get_elapsed()
+	}
+
     /* Destroy chart terminal and return to normal terminal */
-	pub fn restore(self) -> Result<(), Error> {
+	pub fn restore(self) {
+		drop(self);
+	}
+}
+
+/* Terminal is restored however it is dropped, including when returning early with an error */
+impl Drop for Terminal {
+	fn drop(&mut self) {
 		ratatui::restore();
-		Ok(())
 	}
 }
 
 
-impl Renderer for Terminal {
-	fn render(&mut self, snapshot: SortSnapshot) -> Result<(), Error> {
+impl Terminal {
+	/* Wait for duration, handling playback keys */
+	fn playback(&mut self, duration: Duration) -> Result<Control, Error> {
+		if duration != self.interval {
+			self.interval = duration;
+			self.redraw()?;
+		}
+
+		let deadline = Instant::now() + duration;
+
+		loop {
+			let timeout = if self.paused {
+				PAUSE_POLL
+			} else {
+				deadline.saturating_duration_since(Instant::now())
+			};
+
+			if !event::poll(timeout)? {
+				if self.paused {
+					continue;
+				}
+
+				return Ok(Control::Continue);
+			}
+
+			let key = match event::read()? {
+				Event::Key(key) if key.kind == KeyEventKind::Press => key,
+				/* Lay out again for the new size, even while paused */
+				Event::Resize(..) => {
+					self.redraw()?;
+					continue;
+				},
+				_ => continue,
+			};
+
+			if is_quit(&key) {
+				return Ok(Control::Quit);
+			}
+
+			match key.code {
+				KeyCode::Char(' ') => {
+					self.paused = !self.paused;
+					self.redraw()?;
+				},
+				KeyCode::Char('.') | KeyCode::Right if self.paused => return Ok(Control::Continue),
+				KeyCode::Char('+') | KeyCode::Char('=') => return Ok(Control::Faster),
+				KeyCode::Char('-') => return Ok(Control::Slower),
+				KeyCode::Char('t') => {
+					self.show_panel = !self.show_panel;
+					self.redraw()?;
+				},
+				KeyCode::Char('c') => {
+					self.show_code = !self.show_code;
+					self.redraw()?;
+				},
+				KeyCode::Char('v') => {
+					self.view = self.view.next();
+					self.redraw()?;
+				},
+				_ => (),
+			}
+		}
+	}
+
+	/* Draw current snapshot, with the first swept bars in the finished colour */
+	fn draw(&mut self, swept: usize) -> Result<(), Error> {
+		let Some(snapshot) = &self.snapshot else {
+			return Ok(());
+		};
+
+		let status = self.status();
+		let timeline = self.timeline;
+		let show_panel = self.show_panel;
+		let show_code = self.show_code;
+		let view = self.view;
+
 		self.term.draw(|frame| {
-			render_graph(frame, &snapshot).unwrap();
-			if snapshot.is_sorted() {
-				render_popup(frame, &snapshot);
+			let mut chart_area = frame.area();
+			let quantity = snapshot.get_data().len();
+
+			/* Panels show if bars still fit beside them, or if the chart is too dense for bars anyway */
+			let dense = BarSettings::calc(chart_area.width, quantity).is_err();
+
+			/* Show pseudocode before chart if both fit */
+			if show_code {
+				let [code, chart] = Layout::horizontal([
+						Constraint::Length(code_width(snapshot)),
+						Constraint::Fill(1),
+					]).areas(chart_area);
+
+				if dense || BarSettings::calc(chart.width, quantity).is_ok() {
+					chart_area = chart;
+					render_code(frame, code, snapshot);
+				}
+			}
+
+			/* Show heap tree, buckets or call stack beside chart if both fit */
+			if show_panel && has_panel(snapshot) {
+				let [chart, panel] = Layout::horizontal([
+						Constraint::Fill(1),
+						Constraint::Percentage(PANEL_PERCENT),
+					]).areas(chart_area);
+
+				if dense || BarSettings::calc(chart.width, quantity).is_ok() {
+					chart_area = chart;
+					render_panel(frame, panel, snapshot);
+				}
+			}
+
+			/* Replace everything with a placeholder until the terminal is large enough again */
+			if render_graph(frame, chart_area, snapshot, view, swept, &status, CHART_MARGIN).is_err() {
+				render_too_small(frame, frame.area(), quantity);
+				return;
+			}
+
+			if let Some((step, total_steps)) = timeline {
+				render_timeline(frame, step, total_steps, snapshot.get_sort_type().color());
+			}
+			if snapshot.is_sorted() && swept == snapshot.get_data().len() {
+				render_popup(frame, snapshot);
 			}
 		})?;
-				
+
 		Ok(())
 	}
 
-	/* Returns other io error if interrupted */
-	fn sleep(&self, duration: Duration) -> Result<(), Error> {
-		if event::poll(duration)? {
-			if let Event::Key(key) = event::read()? {
-				if key.kind == KeyEventKind::Press {
-					if let KeyCode::Char('q') | KeyCode::Esc = key.code {
-						return Err(Error::Interrupted);
+	/* Draw each racing sort in a grid, with the leaderboard once all have finished */
+	fn draw_race(&mut self) -> Result<(), Error> {
+		let status = self.status();
+		let lanes = &self.race;
+		let view = self.view;
+
+		self.term.draw(|frame| {
+			let columns = (lanes.len() as f64).sqrt().ceil() as usize;
+			let rows = Layout::vertical(vec![Constraint::Fill(1); lanes.len().div_ceil(columns)]).split(frame.area());
+
+			for (index, (snapshot, standing)) in lanes.iter().enumerate() {
+				let cells = Layout::horizontal(vec![Constraint::Fill(1); columns]).split(rows[index / columns]);
+
+				let (swept, status) = match standing {
+					Standing::Racing => (0, status.clone()),
+					Standing::Placed(place) => (snapshot.get_data().len(), ordinal(*place)),
+					Standing::DidNotFinish => (0, String::from("DNF")),
+				};
+
+				if render_graph(frame, cells[index % columns], snapshot, view, swept, &status, RACE_MARGIN).is_err() {
+					render_too_small(frame, cells[index % columns], snapshot.get_data().len());
+				}
+			}
+
+			if lanes.iter().all(|(_, standing)| *standing != Standing::Racing) {
+				render_leaderboard(frame, lanes);
+			}
+		})?;
+
+		Ok(())
+	}
+
+	/* Draw current snapshot, sweeping all bars if finished */
+	fn redraw(&mut self) -> Result<(), Error> {
+		if !self.race.is_empty() {
+			return self.draw_race();
+		}
+
+		let swept = self.snapshot.as_ref()
+			.filter(|snapshot| snapshot.is_finished())
+			.map_or(0, |snapshot| snapshot.get_data().len());
+
+		self.draw(swept)
+	}
+
+	/* Playback state shown in chart title */
+	fn status(&self) -> String {
+		if self.timeline.is_some() {
+			String::from("timeline")
+		} else if self.snapshot.as_ref().is_some_and(|snapshot| snapshot.is_finished()) {
+			String::new()
+		} else if self.paused {
+			String::from("paused")
+		} else {
+			format!("{} ms", self.interval.as_millis())
+		}
+	}
+}
+
+
+impl Renderer for Terminal {
+	fn render(&mut self, event: &SortEvent) -> Result<(), Error> {
+		/* Time spent rendering is not counted as sorting */
+		match event {
+			SortEvent::Start { .. } => self.stopwatch = Stopwatch::new(),
+			_ => self.stopwatch.pause(),
+		}
+
+		/* Reconstruct snapshot from event */
+		match &mut self.snapshot {
+			Some(snapshot) => snapshot.apply(event),
+			None => self.snapshot = SortSnapshot::from_event(event),
+		}
+
+		if let Some(snapshot) = &self.snapshot {
+			self.history.record(event, snapshot);
+		}
+
+		/* Traces are drawn with the operation that follows them */
+		if let SortEvent::Trace { .. } = event {
+			self.stopwatch.resume();
+			return Ok(());
+		}
+
+		/* Sweep finished colour across bars once sorted, still answering quit and resize */
+		if let (SortEvent::Finish, Some(snapshot)) = (event, &self.snapshot) {
+			let len = snapshot.get_data().len();
+			let frames = len.min(SWEEP_FRAMES);
+
+			'sweep: for frame in 0..frames {
+				let swept = frame * len / frames;
+				self.draw(swept)?;
+
+				let deadline = Instant::now() + SWEEP_DURATION / frames as u32;
+				while event::poll(deadline.saturating_duration_since(Instant::now()))? {
+					match event::read()? {
+						Event::Key(key) if key.kind == KeyEventKind::Press && is_quit(&key) => {
+							self.quit = true;
+							break 'sweep;
+						},
+						Event::Resize(..) => self.draw(swept)?,
+						_ => (),
 					}
 				}
 			}
 		}
 
+		self.redraw()
+	}
+
+	/* Wait for duration, then return to sorting */
+	fn sleep(&mut self, duration: Duration) -> Result<Control, Error> {
+		let control = self.playback(duration);
+		self.stopwatch.resume();
+
+		control
+	}
+
+	/* Scrub through recorded steps until quit */
+	fn finish(&mut self) -> Result<(), Error> {
+		if self.quit {
+			return Ok(());
+		}
+
+		/* Traces only move the pseudocode along, so are passed over to match the step count */
+		let operations = self.history.operations();
+		let last = operations.len().saturating_sub(1);
+		let mut position = last;
+
+		loop {
+			self.timeline = Some((position, last));
+			self.snapshot = operations.get(position).and_then(|&step| self.history.snapshot_at(step));
+			self.redraw()?;
+
+			if let Event::Key(key) = event::read()? {
+				if key.kind == KeyEventKind::Release {
+					continue;
+				}
+
+				if is_quit(&key) {
+					break;
+				}
+
+				position = match key.code {
+					KeyCode::Left => position.saturating_sub(1),
+					KeyCode::Right => (position + 1).min(last),
+					KeyCode::Home => 0,
+					KeyCode::End => last,
+					KeyCode::Char('t') => {
+						self.show_panel = !self.show_panel;
+						position
+					},
+					KeyCode::Char('c') => {
+						self.show_code = !self.show_code;
+						position
+					},
+					KeyCode::Char('v') => {
+						self.view = self.view.next();
+						position
+					},
+					_ => position,
+				};
+			}
+		}
+
+		self.timeline = None;
 		Ok(())
 	}
 }
 
 
+/* Check if key quits, including ctrl+c as raw mode stops it interrupting */
+fn is_quit(key: &KeyEvent) -> bool {
+	match key.code {
+		KeyCode::Char('q') | KeyCode::Esc => true,
+		KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+		_ => false,
+	}
+}
+
+
 /* Render bar graph */
-fn render_graph(frame: &mut Frame, snapshot: &SortSnapshot) -> Result<(), Error> {
+fn render_graph(frame: &mut Frame, area: Rect, snapshot: &SortSnapshot, view: View, swept: usize, status: &str, margin: u16) -> Result<(), Error> {
 	let data = snapshot.get_data();
 	let sort_type = snapshot.get_sort_type();
 	
-	/* Calculate bar width and gaps */
-	let bar_settings = BarSettings::calc(frame.area().width, data.len())?;
+	/* Drop margins if they leave too few rows, failing if there are too few anyway */
+	if area.height < MIN_CHART_HEIGHT {
+		return Err(Error::BarOverflow(data.len()));
+	}
+
+	let margin = if area.height >= MIN_CHART_HEIGHT + 2 * margin { margin } else { 0 };
+
+	/* Calculate bar width and gaps, drawing densely if bars would be narrower than a column */
+	let bar_settings = BarSettings::calc(area.width, data.len()).ok().filter(|_| view == View::Bars);
 
 	/* Chart Width = n * (width + gap) - extra gap + padding */
-	let chart_width = (data.len() as u16 * (bar_settings.width + bar_settings.gap)) - bar_settings.gap + CHART_PAD;
+	let chart_width = match (view, bar_settings) {
+		(_, Some(bar_settings)) => (data.len() as u16 * (bar_settings.width + bar_settings.gap)) - bar_settings.gap,
+		(View::Bars | View::Dots, None) => dense_width(area.width, data.len())?,
+		(View::Hue, None) => hue_width(area.width, data.len())?,
+		(View::Disparity, None) => usable_width(area.width, data.len())?,
+	} + CHART_PAD;
 	
 	/* Set up layout of chart - set width and center */
 	let [_, area, _] = Layout::horizontal([
 			Constraint::Fill(1),
 			Constraint::Length(chart_width + HORIZ_PAD), 
 			Constraint::Fill(1),
-		]).vertical_margin(5) 
-		.areas(frame.area());
+		]).vertical_margin(margin) 
+		.areas(area);
+
+	/* Show auxiliary buffers below the bars for sorts that use them */
+	let (area, buffer_area) = if snapshot.uses_buffers() && view == View::Bars {
+		let [chart, _, buffers] = Layout::vertical([
+				Constraint::Fill(1),
+				Constraint::Length(1),
+				Constraint::Percentage(BUFFER_PERCENT),
+			]).areas(area);
+
+		(chart, Some(buffers))
+	} else {
+		(area, None)
+	};
 	
-	/* Set up containing block */
+	/* Set up containing block, showing options and gap of shell sort */
+	let details: Vec<String> = snapshot.get_variant().into_iter()
+		.chain(snapshot.get_gap().map(|gap| format!("gap {}", gap)))
+		.collect();
+
+	let title = if details.is_empty() {
+		sort_type.to_string()
+	} else {
+		format!("{} ({})", sort_type, details.join(", "))
+	};
+
 	let block = Block::default()
-		.title(Line::styled(sort_type.to_string(), sort_type.color()).bold())
+		.title(Line::styled(title, sort_type.color()).bold())
+		.title(Line::raw(status).right_aligned())
+		.title_bottom(Line::raw(snapshot.get_metrics().summary()).centered())
 		.padding(Padding::new(HORIZ_PAD / 2, HORIZ_PAD / 2, 2, 0))
 		.borders(Borders::ALL);
 
-	/* Set up bar chart */
+	let runs_area = Rect::new(area.x + 1 + HORIZ_PAD / 2, area.bottom(), chart_width - CHART_PAD, 1)
+		.intersection(frame.area());
+
+	match (view, bar_settings) {
+		(_, Some(bar_settings)) => {
+			/* Set up bar chart */
+			let bar_chart = BarChart::default()
+				.block(block)
+				.bar_style(Style::default().fg(sort_type.color()))
+				.bar_width(bar_settings.width)
+				.bar_gap(bar_settings.gap)
+				.max(max_value(data))
+				.data(build_bars(bar_settings, snapshot, swept));
+
+			/* Render bar chart with set area */
+			frame.render_widget(bar_chart, area);
+
+			/* Render run brackets below the chart */
+			let stride = (bar_settings.width + bar_settings.gap) as usize;
+			let columns = |index: usize| (index * stride, index * stride + bar_settings.width as usize - 1);
+			frame.render_widget(run_brackets(snapshot.get_runs(), columns), runs_area);
+
+			if let Some(buffer_area) = buffer_area {
+				render_buffers(frame, buffer_area, snapshot, bar_settings);
+			}
+		},
+		(View::Bars, None) => {
+			let inner = block.inner(area);
+			let values: Vec<Option<(u64, Color)>> = data.iter()
+				.enumerate()
+				.map(|(index, value)| Some((*value, bar_color(snapshot, index, swept))))
+				.collect();
+
+			frame.render_widget(block, area);
+			frame.render_widget(Paragraph::new(dense_lines(&values, max_value(data), inner.width, inner.height)), inner);
+
+			let width = runs_area.width as usize;
+			let columns = |index: usize| (dense_column(index, data.len(), width), dense_column(index, data.len(), width));
+			frame.render_widget(run_brackets(snapshot.get_runs(), columns), runs_area);
+
+			if let Some(buffer_area) = buffer_area {
+				render_dense_buffers(frame, buffer_area, snapshot);
+			}
+		},
+		(View::Dots, None) => render_dots(frame, area, block, snapshot, swept),
+		(View::Hue, None) => render_hue(frame, area, block, snapshot, swept),
+		(View::Disparity, None) => render_disparity(frame, area, block, snapshot, swept),
+	}
+
+	Ok(())
+}
+
+/* Columns of a hue strip, giving every value the same number of columns if they all fit */
+fn hue_width(term_width: u16, quantity: usize) -> Result<u16, Error> {
+	let usable_term_width = usable_width(term_width, quantity)?;
+
+	Ok(match usable_term_width / (quantity as u16).max(1) {
+		0 => usable_term_width,
+		columns => columns * quantity as u16,
+	})
+}
+
+/* Plot each value as a braille dot against its index */
+fn render_dots(frame: &mut Frame, area: Rect, block: Block, snapshot: &SortSnapshot, swept: usize) {
+	let data = snapshot.get_data();
+
+	let points = point_groups(data.iter()
+		.enumerate()
+		.map(|(index, value)| (bar_color(snapshot, index, swept), (index as f64 + 0.5, *value as f64))));
+
+	let canvas = Canvas::default()
+		.block(block)
+		.marker(Marker::Braille)
+		.x_bounds([0.0, data.len() as f64])
+		.y_bounds([0.0, max_value(data) as f64])
+		.paint(|ctx| {
+			for (color, coords) in &points {
+				ctx.draw(&Points { coords, color: *color });
+			}
+		});
+
+	frame.render_widget(canvas, area);
+}
+
+/* Draw each value as a column coloured by its hue, above a row marking the colour of its bar */
+fn render_hue(frame: &mut Frame, area: Rect, block: Block, snapshot: &SortSnapshot, swept: usize) {
+	let data = snapshot.get_data();
+	let max = max_value(data);
+	let inner = block.inner(area);
+	let width = inner.width as usize;
+
+	/* Value shown in each column, and the colours of the bars it covers */
+	let columns: Vec<(u64, Color)> = (0 .. width)
+		.map(|column| {
+			let first = column * data.len() / width;
+			let last = ((column + 1) * data.len() / width).max(first + 1);
+			let colors: Vec<Color> = (first .. last).map(|index| bar_color(snapshot, index, swept)).collect();
+
+			(data[first], cell_color(&colors))
+		})
+		.collect();
+
+	let strip = Line::from(columns.iter()
+		.map(|(value, _)| Span::styled("\u{2588}", hue(*value, max)))
+		.collect::<Vec<Span>>());
+
+	let mut lines = vec![strip; inner.height.saturating_sub(1) as usize];
+	lines.push(Line::from(columns.iter()
+		.map(|(_, color)| Span::styled("\u{2580}", *color))
+		.collect::<Vec<Span>>()));
+
+	frame.render_widget(block, area);
+	frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/* Plot each value around a circle by its index, its distance from the centre showing how far it is from its sorted position */
+fn render_disparity(frame: &mut Frame, area: Rect, block: Block, snapshot: &SortSnapshot, swept: usize) {
+	let data = snapshot.get_data();
+	let furthest = data.len().saturating_sub(1).max(1) as f64;
+
+	/* Sorted position of each value, equal values keeping their order */
+	let mut order: Vec<usize> = (0 .. data.len()).collect();
+	order.sort_by_key(|index| data[*index]);
+
+	let mut positions = vec![0; data.len()];
+	for (position, index) in order.into_iter().enumerate() {
+		positions[index] = position;
+	}
+
+	let points = point_groups(positions.iter()
+		.enumerate()
+		.map(|(index, position)| {
+			let radius = index.abs_diff(*position) as f64 / furthest;
+			let angle = std::f64::consts::TAU * index as f64 / data.len() as f64;
+
+			(bar_color(snapshot, index, swept), (radius * angle.sin(), radius * angle.cos()))
+		}));
+
+	/* Braille dots are about as wide as they are tall, so bounds keep the circle round */
+	let inner = block.inner(area);
+	let aspect = (inner.width as f64 * DENSE_DOTS_ACROSS as f64) / (inner.height.max(1) as f64 * DENSE_DOTS_DOWN as f64);
+
+	let canvas = Canvas::default()
+		.block(block)
+		.marker(Marker::Braille)
+		.x_bounds([-aspect, aspect])
+		.y_bounds([-1.0, 1.0])
+		.paint(|ctx| {
+			ctx.draw(&Circle { x: 0.0, y: 0.0, radius: 1.0, color: OUTSIDE_COLOR });
+			ctx.layer();
+
+			for (color, coords) in &points {
+				ctx.draw(&Points { coords, color: *color });
+			}
+		});
+
+	frame.render_widget(canvas, area);
+}
+
+/* Group points by colour, with highlights last so they are drawn over their neighbours */
+fn point_groups(points: impl Iterator<Item = (Color, (f64, f64))>) -> Vec<(Color, Vec<(f64, f64)>)> {
+	let mut groups: Vec<(Color, Vec<(f64, f64)>)> = Vec::new();
+
+	for (color, point) in points {
+		match groups.iter_mut().find(|(group, _)| *group == color) {
+			Some((_, coords)) => coords.push(point),
+			None => groups.push((color, vec![point])),
+		}
+	}
+
+	groups.sort_by_key(|(color, _)| HIGHLIGHT_COLORS.iter().rev().position(|highlight| highlight == color));
+	groups
+}
+
+/* Colour of value along the spectrum from red to violet */
+fn hue(value: u64, max: u64) -> Color {
+	let hue = HUE_RANGE * value as f64 / max.max(1) as f64 / 60.0;
+	let rising = ((1.0 - (hue % 2.0 - 1.0).abs()) * 255.0) as u8;
+
+	let (r, g, b) = match hue as u32 {
+		0 => (255, rising, 0),
+		1 => (rising, 255, 0),
+		2 => (0, 255, rising),
+		3 => (0, rising, 255),
+		4 => (rising, 0, 255),
+		_ => (255, 0, rising),
+	};
+
+	Color::Rgb(r, g, b)
+}
+
+/* Colour of a cell showing several values, from its most important highlight */
+fn cell_color(colors: &[Color]) -> Color {
+	HIGHLIGHT_COLORS.iter()
+		.find(|color| colors.contains(color))
+		.or(colors.first())
+		.copied()
+		.unwrap_or_default()
+}
+
+/* Columns left for a chart of quantity values, failing if there are none */
+fn usable_width(term_width: u16, quantity: usize) -> Result<u16, Error> {
+	match term_width.saturating_sub(HORIZ_PAD + CHART_PAD) {
+		0 => Err(Error::BarOverflow(quantity)),
+		usable_term_width => Ok(usable_term_width),
+	}
+}
+
+/* Columns of a dense chart, fitting two values per column where possible */
+fn dense_width(term_width: u16, quantity: usize) -> Result<u16, Error> {
+	let usable_term_width = usable_width(term_width, quantity)?;
+
+	Ok(quantity.div_ceil(DENSE_DOTS_ACROSS).min(usable_term_width as usize) as u16)
+}
+
+/* Column of a dense chart of width columns that the value at index is drawn in */
+fn dense_column(index: usize, quantity: usize, width: usize) -> usize {
+	index * width * DENSE_DOTS_ACROSS / quantity / DENSE_DOTS_ACROSS
+}
+
+/* Draw values as braille columns, several values sharing a column of dots if there are too many to fit.
+ * Each cell takes the colour of its most important highlight */
+fn dense_lines(values: &[Option<(u64, Color)>], max: u64, width: u16, height: u16) -> Vec<Line<'static>> {
+	let (width, height) = (width as usize, height as usize);
+	let dots_down = height * DENSE_DOTS_DOWN;
+
+	/* Tallest value and the colours of the values in each column of dots */
+	let mut heights = vec![0; width * DENSE_DOTS_ACROSS];
+	let mut colors: Vec<Vec<Color>> = vec![Vec::new(); width];
+
+	for (index, (value, color)) in values.iter().enumerate().filter_map(|(index, value)| value.map(|value| (index, value))) {
+		let dot = index * heights.len() / values.len();
+		let dot_height = (value as usize * dots_down).div_ceil(max.max(1) as usize).min(dots_down);
+
+		heights[dot] = heights[dot].max(dot_height);
+		colors[dot / DENSE_DOTS_ACROSS].push(color);
+	}
+
+	(0 .. height)
+		.map(|row| {
+			/* Height in dots of the bottom of this row */
+			let floor = (height - row - 1) * DENSE_DOTS_DOWN;
+
+			Line::from((0 .. width)
+				.map(|column| {
+					let mut cell = 0;
+
+					for (across, dot_bits) in BRAILLE_DOTS.iter().enumerate() {
+						let filled = heights[column * DENSE_DOTS_ACROSS + across].saturating_sub(floor);
+
+						/* Fill dots from the bottom of the cell */
+						for bit in &dot_bits[DENSE_DOTS_DOWN - filled.min(DENSE_DOTS_DOWN) ..] {
+							cell |= bit;
+						}
+					}
+
+					Span::styled(char::from_u32(BRAILLE_BLANK + cell).unwrap_or(' ').to_string(), cell_color(&colors[column]))
+				})
+				.collect::<Vec<Span>>())
+		})
+		.collect()
+}
+
+/* Render live auxiliary buffers densely, each value under the column it was copied from */
+fn render_dense_buffers(frame: &mut Frame, area: Rect, snapshot: &SortSnapshot) {
+	let data = snapshot.get_data();
+	let buffers = snapshot.get_buffers();
+
+	let block = Block::default()
+		.title(Line::styled("Auxiliary", snapshot.get_sort_type().color()).bold())
+		.padding(Padding::new(HORIZ_PAD / 2, HORIZ_PAD / 2, 1, 0))
+		.borders(Borders::ALL);
+
+	let values: Vec<Option<(u64, Color)>> = (0 .. data.len())
+		.map(|index| buffers.iter()
+			.find(|buffer| buffer.covers(index))
+			.map(|buffer| {
+				let offset = index - buffer.get_start();
+				(buffer.get_values()[offset], buffer_color(snapshot, buffer, index))
+			}))
+		.collect();
+
+	let inner = block.inner(area);
+	frame.render_widget(block, area);
+	frame.render_widget(Paragraph::new(dense_lines(&values, max_value(data), inner.width, inner.height)), inner);
+}
+
+/* Render live auxiliary buffers, each value under the bar it was copied from, with read cursors marked */
+fn render_buffers(frame: &mut Frame, area: Rect, snapshot: &SortSnapshot, bar_settings: BarSettings) {
+	let sort_type = snapshot.get_sort_type();
+	let data = snapshot.get_data();
+	let buffers = snapshot.get_buffers();
+
+	let block = Block::default()
+		.title(Line::styled("Auxiliary", sort_type.color()).bold())
+		.padding(Padding::new(HORIZ_PAD / 2, HORIZ_PAD / 2, 1, 0))
+		.borders(Borders::ALL);
+
+	let bars: Vec<Bar> = (0 .. data.len())
+		.map(|index| match buffers.iter().enumerate().find(|(_, buffer)| buffer.covers(index)) {
+			Some((number, buffer)) => buffer_bar(snapshot, buffer, number, index),
+			None => Bar::default().value(0).text_value(String::new()).label(Line::from("")),
+		})
+		.collect();
+
 	let bar_chart = BarChart::default()
 		.block(block)
-		.bar_style(Style::default().fg(sort_type.color()))
 		.bar_width(bar_settings.width)
 		.bar_gap(bar_settings.gap)
-		.max(data.len() as u64)
-		.data(build_bars(bar_settings, &data));
+		.max(max_value(data))
+		.data(BarGroup::default().bars(&bars));
 
-	/* Render bar chart with set area */
 	frame.render_widget(bar_chart, area);
+}
 
-	Ok(())
+/* Build bar for the buffered copy of data[index], labelled if under the cursor */
+fn buffer_bar<'a>(snapshot: &SortSnapshot, buffer: &Buffer, number: usize, index: usize) -> Bar<'a> {
+	let offset = index - buffer.get_start();
+	let cursor = buffer.get_cursor();
+	let color = buffer_color(snapshot, buffer, index);
+
+	let label = if offset == cursor { CURSOR_NAMES.get(number).copied().unwrap_or("") } else { "" };
+
+	Bar::default()
+		.value(buffer.get_values()[offset])
+		.text_value(String::new())
+		.label(Line::from(label))
+		.style(Style::default().fg(color))
+}
+
+/* Colour of the buffered copy of data[index], dimmed once read */
+fn buffer_color(snapshot: &SortSnapshot, buffer: &Buffer, index: usize) -> Color {
+	match snapshot.buffer_highlight(index) {
+		Some(Highlight::Compared) => COMPARED_COLOR,
+		Some(Highlight::Changed) => CHANGED_COLOR,
+		_ if index - buffer.get_start() < buffer.get_cursor() => READ_COLOR,
+		_ => snapshot.get_sort_type().color(),
+	}
+}
+
+/* Line of brackets spanning the columns of each run, skipping runs sharing a column with the one before */
+fn run_brackets(runs: &[(usize, usize)], columns: impl Fn(usize) -> (usize, usize)) -> Line<'static> {
+	let mut spans: Vec<Span> = Vec::new();
+	let mut cursor = 0;
+
+	for (index, (start, end)) in runs.iter().enumerate() {
+		let (left, _) = columns(*start);
+		let (_, right) = columns(end - 1);
+
+		if left < cursor {
+			continue;
+		}
+
+		let bracket = match right - left {
+			0 => String::from("│"),
+			inner => format!("└{}┘", "─".repeat(inner - 1)),
+		};
+
+		spans.push(Span::raw(" ".repeat(left - cursor)));
+		spans.push(Span::styled(bracket, RUN_COLORS[index % RUN_COLORS.len()]));
+		cursor = right + 1;
+	}
+
+	Line::from(spans)
+}
+
+
+/* Width of pseudocode panel, fitting the longest line */
+fn code_width(snapshot: &SortSnapshot) -> u16 {
+	let longest = snapshot.get_pseudocode().iter()
+		.map(|line| line.chars().count())
+		.max()
+		.unwrap_or(0);
+
+	longest as u16 + CODE_PAD
+}
+
+/* Render pseudocode with the current line highlighted, followed by the values of its variables */
+fn render_code(frame: &mut Frame, area: Rect, snapshot: &SortSnapshot) {
+	let sort_type = snapshot.get_sort_type();
+
+	let [area, _] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)])
+		.vertical_margin(CHART_MARGIN)
+		.areas(area);
+
+	let block = Block::default()
+		.title(Line::styled("Pseudocode", sort_type.color()).bold())
+		.padding(Padding::new(1, 1, 1, 0))
+		.borders(Borders::ALL);
+
+	let mut lines: Vec<Line> = snapshot.get_pseudocode().iter()
+		.enumerate()
+		.map(|(index, text)| {
+			let line = Line::raw(format!("{:>2}  {}", index + 1, text));
+
+			if snapshot.get_line() == Some(index + 1) {
+				line.style(Style::default().fg(Color::Black).bg(sort_type.color()))
+			} else {
+				line
+			}
+		})
+		.collect();
+
+	lines.push(Line::raw(""));
+	lines.extend(snapshot.get_vars().iter()
+		.map(|(name, value)| Line::styled(format!("    {} = {}", name, value), COMPARED_COLOR)));
+
+	frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/* Check if sort has a structure to show in the side panel */
+fn has_panel(snapshot: &SortSnapshot) -> bool {
+	snapshot.get_heap().is_some()
+		|| !snapshot.get_buckets().is_empty()
+		|| snapshot.is_divide_and_conquer()
+}
+
+/* Render side panel for sorts with auxiliary structures */
+fn render_panel(frame: &mut Frame, area: Rect, snapshot: &SortSnapshot) {
+	let [_, area] = Layout::horizontal([Constraint::Length(1), Constraint::Fill(1)])
+		.vertical_margin(CHART_MARGIN)
+		.areas(area);
+
+	match snapshot.get_heap() {
+		Some(heap) => render_tree(frame, area, snapshot, heap),
+		None if !snapshot.get_buckets().is_empty() => render_buckets(frame, area, snapshot),
+		None => render_stack(frame, area, snapshot),
+	}
+}
+
+/* Render range of each active recursive call, innermost last */
+fn render_stack(frame: &mut Frame, area: Rect, snapshot: &SortSnapshot) {
+	let sort_type = snapshot.get_sort_type();
+	let stack = snapshot.get_stack();
+
+	let block = Block::default()
+		.title(Line::styled("Call stack", sort_type.color()).bold())
+		.title(Line::raw(format!("depth {}", stack.len())).right_aligned())
+		.padding(Padding::new(HORIZ_PAD / 2, HORIZ_PAD / 2, 1, 0))
+		.borders(Borders::ALL);
+
+	/* Keep innermost calls visible, replacing outer ones with a count if they don't fit */
+	let height = block.inner(area).height as usize;
+	let hidden = if stack.len() > height { stack.len() - height + 1 } else { 0 };
+
+	let mut lines: Vec<Line> = Vec::new();
+	if hidden > 0 {
+		lines.push(Line::raw(format!("\u{22EE} {} more", hidden)));
+	}
+
+	lines.extend(stack.iter().enumerate().skip(hidden).map(|(depth, (start, end))| {
+		let line = Line::raw(format!("{:>3}  ({}, {})", depth + 1, start, end));
+
+		if depth + 1 == stack.len() {
+			line.style(Style::default().fg(sort_type.color()).bold())
+		} else {
+			line
+		}
+	}));
+
+	frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/* Render heap in data[..heap] as a binary tree, showing as many levels as fit */
+fn render_tree(frame: &mut Frame, area: Rect, snapshot: &SortSnapshot, heap: usize) {
+	let sort_type = snapshot.get_sort_type();
+	let data = snapshot.get_data();
+
+	let block = Block::default()
+		.title(Line::styled("Heap", sort_type.color()).bold())
+		.borders(Borders::ALL);
+
+	let inner = block.inner(area);
+	let node_width = max_value(data).ilog10() as usize + 2;
+
+	let mut lines: Vec<Line> = Vec::new();
+	let mut level: u32 = 0;
+
+	loop {
+		let first = (1 << level) - 1;
+		let slots = 1_usize << level;
+		let slot_width = inner.width as usize / slots;
+
+		/* Stop once heap is exhausted or level doesn't fit */
+		if first >= heap || slot_width < node_width || lines.len() + 2 > inner.height as usize {
+			break;
+		}
+
+		let nodes = first .. (first + slots).min(heap);
+
+		if level > 0 {
+			lines.push(tree_line(nodes.clone().map(|index| {
+				let branch = if index % 2 == 1 { "\u{2571}" } else { "\u{2572}" };
+				(index - first, Span::styled(branch, Style::default().fg(sort_type.color())))
+			}), slot_width));
+		}
+
+		lines.push(tree_line(nodes.map(|index| {
+			(index - first, Span::styled(data[index].to_string(), bar_color(snapshot, index, 0)))
+		}), slot_width));
+
+		level += 1;
+	}
+
+	frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/* Render number of values in each bucket as a bar chart */
+fn render_buckets(frame: &mut Frame, area: Rect, snapshot: &SortSnapshot) {
+	let sort_type = snapshot.get_sort_type();
+	let buckets = snapshot.get_buckets();
+
+	let block = Block::default()
+		.title(Line::styled("Buckets", sort_type.color()).bold())
+		.title(Line::raw(format!("pass {}", snapshot.get_pass())).right_aligned())
+		.padding(Padding::new(HORIZ_PAD / 2, HORIZ_PAD / 2, 1, 0))
+		.borders(Borders::ALL);
+
+	let bar_settings = BarSettings::calc(area.width, buckets.len())
+		.unwrap_or(BarSettings { width: 1, gap: 0 });
+	let label_pows = buckets.len().max(1).ilog10() + 1;
+
+	let bars: Vec<Bar> = buckets.iter()
+		.enumerate()
+		.map(|(bucket, values)| {
+			let color = if snapshot.get_bucket() == Some(bucket) { COMPARED_COLOR } else { sort_type.color() };
+			let label = if label_pows <= bar_settings.width as u32 { bucket.to_string() } else { String::new() };
+
+			Bar::default()
+				.value(values.len() as u64)
+				.text_value(String::new())
+				.label(Line::from(label))
+				.style(Style::default().fg(color))
+		})
+		.collect();
+
+	let max = buckets.iter().map(|values| values.len() as u64).max().unwrap_or(0).max(1);
+
+	let bar_chart = BarChart::default()
+		.block(block)
+		.bar_width(bar_settings.width)
+		.bar_gap(bar_settings.gap)
+		.max(max)
+		.data(BarGroup::default().bars(&bars));
+
+	frame.render_widget(bar_chart, area);
+}
+
+/* Build line with each span centred in its slot */
+fn tree_line<'a>(spans: impl Iterator<Item = (usize, Span<'a>)>, slot_width: usize) -> Line<'a> {
+	let mut line = Line::default();
+	let mut cursor = 0;
+
+	for (slot, span) in spans {
+		let start = (slot * slot_width + slot_width / 2).saturating_sub(span.width() / 2);
+
+		line.push_span(" ".repeat(start.saturating_sub(cursor)));
+		cursor = start.max(cursor) + span.width();
+		line.push_span(span);
+	}
+
+	line
 }
 
 
+/* Render message in place of a chart that doesn't fit in area */
+fn render_too_small(frame: &mut Frame, area: Rect, quantity: usize) {
+	let text = Text::from(vec![
+		Line::raw("Terminal too small").bold(),
+		Line::raw(format!("to show {} values", quantity)),
+		Line::styled("Resize to continue", Color::DarkGray),
+	]);
+
+	let [_, text_area, _] = Layout::vertical([
+			Constraint::Fill(1),
+			Constraint::Length(text.height() as u16),
+			Constraint::Fill(1),
+		]).areas(area);
+
+	frame.render_widget(Clear, area);
+	frame.render_widget(Paragraph::new(text).centered(), text_area);
+}
+
 /* Render popup to show sorted */
 fn render_popup(frame: &mut Frame, snapshot: &SortSnapshot) {
 	let sort_type = snapshot.get_sort_type();
@@ -173,26 +1196,30 @@ fn render_popup(frame: &mut Frame, snapshot: &SortSnapshot) {
 			Constraint::Fill(1)
 		]).areas(frame.area());
 
-	let [_, popup_area, _] = Layout::vertical([
-			Constraint::Fill(1), 
-			Constraint::Percentage(40), 
-			Constraint::Fill(1)
-		]).areas(horiz_area);
-
-	/* Clear popup area */
-	frame.render_widget(Clear, popup_area);
-	
 	/* Set up containing block */
 	let block = Block::default().borders(Borders::ALL);
 
 	let mut text = Text::from(vec![
 		Line::styled("Sorted!", sort_type.color()).bold(),
-		Line::styled(format!("{}", snapshot.get_count()), sort_type.color()),
+		Line::styled(format!("Seed: {}", snapshot.get_seed()), sort_type.color()),
 		Line::raw(""),
 	]);
+
+	text.extend(snapshot.get_metrics().to_text().lines);
+	text.push_line("");
 	
 	let analytics: Analytics = sort_type.analytics();
-	text.extend(analytics.to_text().lines.into_iter());
+	text.extend(analytics.to_text().lines);
+
+	/* Fit popup height to its text */
+	let [_, popup_area, _] = Layout::vertical([
+			Constraint::Fill(1), 
+			Constraint::Length(text.height() as u16 + 2), 
+			Constraint::Fill(1)
+		]).areas(horiz_area);
+
+	/* Clear popup area */
+	frame.render_widget(Clear, popup_area);
 
  	let popup = Paragraph::new(text)
 		.block(block)
@@ -202,26 +1229,206 @@ fn render_popup(frame: &mut Frame, snapshot: &SortSnapshot) {
 }
 
 
+/* Render algorithms with their complexities, and the settings they will run with */
+fn render_menu(frame: &mut Frame, menu: &Menu) {
+	let sort_types = menu.get_sort_types();
+	let settings = menu.get_settings();
+	let color = menu.get_sort_type().color();
+
+	/* Header and algorithms, settings, then help, separated by blank lines */
+	let height = sort_types.len() + settings.len() + 4;
+
+	let [_, area, _] = Layout::horizontal([
+			Constraint::Fill(1),
+			Constraint::Length(MENU_WIDTH),
+			Constraint::Fill(1),
+		]).areas(frame.area());
+
+	let [_, area, _] = Layout::vertical([
+			Constraint::Fill(1),
+			Constraint::Length(height as u16 + 2),
+			Constraint::Fill(1),
+		]).areas(area);
+
+	let block = Block::default()
+		.title(Line::styled("Sorts TUI", color).bold())
+		.padding(Padding::horizontal(1))
+		.borders(Borders::ALL);
+
+	let [table_area, settings_area, help_area] = Layout::vertical([
+			Constraint::Length(sort_types.len() as u16 + 1),
+			Constraint::Length(settings.len() as u16),
+			Constraint::Length(1),
+		]).spacing(1).areas(block.inner(area));
+
+	frame.render_widget(block, area);
+
+	let rows = sort_types.iter()
+		.enumerate()
+		.map(|(index, sort_type)| {
+			let mut cells = vec![sort_type.to_string()];
+			cells.extend(sort_type.analytics().summary());
+
+			let row = Row::new(cells);
+
+			if index == menu.get_selected() {
+				row.style(Style::default().fg(Color::Black).bg(sort_type.color()))
+			} else {
+				row.style(Style::default().fg(sort_type.color()))
+			}
+		});
+
+	let table = Table::new(rows, [
+			Constraint::Length(MENU_NAME_WIDTH),
+			Constraint::Fill(1),
+			Constraint::Fill(1),
+			Constraint::Fill(1),
+			Constraint::Fill(1),
+		])
+		.header(Row::new(["Algorithm", "Worst time", "Average time", "Best time", "Worst space"]).bold());
+
+	frame.render_widget(table, table_area);
+
+	let lines: Vec<Line> = settings.into_iter()
+		.map(|(setting, value, focused)| {
+			let line = Line::raw(format!("{:<width$}\u{2039} {} \u{203A}", setting.name(), value, width = MENU_NAME_WIDTH as usize + 1));
+
+			if focused {
+				line.style(Style::default().fg(color)).bold()
+			} else {
+				line
+			}
+		})
+		.collect();
+
+	frame.render_widget(Paragraph::new(lines), settings_area);
+	frame.render_widget(Line::styled(MENU_HELP, Color::DarkGray).centered(), help_area);
+}
+
+
+/* Render racing sorts in finishing order */
+fn render_leaderboard(frame: &mut Frame, lanes: &[(SortSnapshot, Standing)]) {
+	let mut standings: Vec<&(SortSnapshot, Standing)> = lanes.iter().collect();
+	standings.sort_by_key(|(_, standing)| match standing {
+		Standing::Placed(place) => *place,
+		_ => usize::MAX,
+	});
+
+	let mut text = Text::from(vec![
+		Line::raw("Finished!").bold(),
+		Line::raw(format!("Seed: {}", lanes.first().map_or(0, |(snapshot, _)| snapshot.get_seed()))),
+		Line::raw(""),
+	]);
+
+	text.extend(standings.iter().map(|(snapshot, standing)| {
+		let sort_type = snapshot.get_sort_type();
+		let place = match standing {
+			Standing::Placed(place) => ordinal(*place),
+			_ => String::from("DNF"),
+		};
+
+		Line::styled(
+			format!("{:<5}{:<16}{:>7} steps", place, sort_type.to_string(), snapshot.get_metrics().steps()),
+			sort_type.color(),
+		)
+	}));
+
+	let [_, horiz_area, _] = Layout::horizontal([
+			Constraint::Fill(1),
+			Constraint::Length(text.width() as u16 + HORIZ_PAD),
+			Constraint::Fill(1),
+		]).areas(frame.area());
+
+	let [_, popup_area, _] = Layout::vertical([
+			Constraint::Fill(1),
+			Constraint::Length(text.height() as u16 + 2),
+			Constraint::Fill(1),
+		]).areas(horiz_area);
+
+	frame.render_widget(Clear, popup_area);
+	frame.render_widget(Paragraph::new(text).block(Block::default().borders(Borders::ALL)).centered(), popup_area);
+}
+
+/* Place in a race as an ordinal, such as 1st */
+fn ordinal(place: usize) -> String {
+	let suffix = match (place % 10, place % 100) {
+		(_, 11..=13) => "th",
+		(1, _) => "st",
+		(2, _) => "nd",
+		(3, _) => "rd",
+		_ => "th",
+	};
+
+	format!("{}{}", place, suffix)
+}
+
+/* Render progress gauge through recorded steps */
+fn render_timeline(frame: &mut Frame, step: usize, total_steps: usize, color: Color) {
+	let [_, area, _] = Layout::vertical([
+			Constraint::Fill(1),
+			Constraint::Length(3),
+			Constraint::Length(1),
+		]).horizontal_margin(HORIZ_PAD)
+		.areas(frame.area());
+
+	let ratio = if total_steps == 0 { 1.0 } else { step as f64 / total_steps as f64 };
+
+	let gauge = Gauge::default()
+		.block(Block::default().borders(Borders::ALL))
+		.gauge_style(Style::default().fg(color))
+		.ratio(ratio)
+		.label(format!("step {} of {}", step, total_steps));
+
+	frame.render_widget(gauge, area);
+}
+
+
 /* Build a bar from value */
-fn bar<'a>(value: u64, max_pows: u32, bar_settings: BarSettings) -> Bar<'a> {
-	let format_val = |x: u64| (max_pows <= bar_settings.width as u32 && bar_settings.gap != 0)
-		.then_some(x.to_string())
-		.unwrap_or(String::from(""));
+fn bar<'a>(value: u64, max_pows: u32, bar_settings: BarSettings, style: Style) -> Bar<'a> {
+	let format_val = |x: u64| if max_pows <= bar_settings.width as u32 && bar_settings.gap != 0 {
+		x.to_string()
+	} else {
+		String::from("")
+	};
 
 	Bar::default()
 		.value(value)
 		.text_value(String::from(""))
 		.label(Line::from(format_val(value)))
+		.style(style)
+}
+
+/* Colour of bar at index, from the last operation */
+fn bar_color(snapshot: &SortSnapshot, index: usize, swept: usize) -> Color {
+	if index < swept {
+		SORTED_COLOR
+	} else {
+		match snapshot.highlight(index) {
+			Some(Highlight::Compared) => COMPARED_COLOR,
+			Some(Highlight::Changed) => CHANGED_COLOR,
+			Some(Highlight::Pivot) => PIVOT_COLOR,
+			None if snapshot.in_chain(index) => CHAIN_COLOR,
+			None if !snapshot.in_active_range(index) => OUTSIDE_COLOR,
+			None => snapshot.get_sort_type().color(),
+		}
+	}
+}
+
+/* Height of the tallest bar, at least the number of bars */
+fn max_value(data: &[u64]) -> u64 {
+	data.iter().copied().max().unwrap_or(0).max(data.len() as u64)
 }
 
 /* Build group of bars from the data */
-fn build_bars(bar_settings: BarSettings, data: &Vec<u64>) -> BarGroup {
-	let max: usize = data.len() + 1;
-	let max_pows: u32 = max.ilog10() + 1 as u32;
+fn build_bars(bar_settings: BarSettings, snapshot: &SortSnapshot, swept: usize) -> BarGroup<'_> {
+	let data = snapshot.get_data();
+	let max: u64 = max_value(data) + 1;
+	let max_pows: u32 = max.ilog10() + 1;
 	
 	BarGroup::default().bars(
 		&data.iter()
-			.map(|value| bar(*value, max_pows, bar_settings))
+			.enumerate()
+			.map(|(index, value)| bar(*value, max_pows, bar_settings, Style::default().fg(bar_color(snapshot, index, swept))))
 			.collect::<Vec<Bar>>()
 	)
 }
@@ -0,0 +1,25 @@
+use clap::ValueEnum;
+
+/* How the data of a sort is drawn */
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+pub enum View {
+	/* Bar for each value, in dense braille columns if there are too many to fit */
+	#[default]
+	Bars,
+	/* Dot for each value, plotted against its index */
+	Dots,
+	/* Strip of colours, each value mapped to a hue */
+	Hue,
+	/* Dot for each value around a circle, further from the centre the further it is from its sorted position */
+	Disparity,
+}
+
+impl View {
+	/* View after this one, wrapping around */
+	pub fn next(&self) -> View {
+		let views = View::value_variants();
+		let index = views.iter().position(|view| view == self).unwrap_or(0);
+
+		views[(index + 1) % views.len()]
+	}
+}